@@ -0,0 +1,46 @@
+//! 账户反序列化，校验账户鉴别码后解析 cpay、drift_vaults 和 drift 的账户数据
+
+use std::mem::size_of;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::{AccountDeserialize, Result, ZeroCopy};
+
+pub use ::cpay::circle::{
+    BurnMessage, MessageTransmitter, RemoteTokenMessenger, TokenMessenger, TokenPair, UsedNonces,
+};
+pub use ::cpay::instructions::GlobalState;
+pub use ::cpay::state::{AccessControl, AllocationConfig, FundAccount, Position};
+pub use ::drift::state::spot_market::SpotMarket;
+pub use ::drift::state::user::User;
+pub use ::drift_vaults::state::{
+    TokenizedVaultDepositor, Vault, VaultDepositor, VaultProtocol,
+};
+
+/// 解析使用 borsh 序列化的账户，如 cpay 的所有账户
+pub fn deserialize<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    let mut data = data;
+    T::try_deserialize(&mut data)
+}
+
+/// 解析 zero_copy 账户，如 drift_vaults 的金库和存款人账户以及 drift 的用户和市场账户
+pub fn deserialize_zero_copy<T: ZeroCopy>(data: &[u8]) -> Result<T> {
+    if data.len() < T::DISCRIMINATOR.len() {
+        return Err(ErrorCode::AccountDiscriminatorNotFound.into());
+    }
+    let (discriminator, data) = data.split_at(T::DISCRIMINATOR.len());
+    if discriminator != T::DISCRIMINATOR {
+        return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    if data.len() < size_of::<T>() {
+        return Err(ErrorCode::AccountDidNotDeserialize.into());
+    }
+    Ok(bytemuck::pod_read_unaligned(&data[..size_of::<T>()]))
+}
+
+/// 序列化 zero_copy 账户数据（含鉴别码），用于构造测试账户
+pub fn serialize_zero_copy<T: ZeroCopy>(account: &T) -> Vec<u8> {
+    let mut data = T::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(account));
+    data
+}
+
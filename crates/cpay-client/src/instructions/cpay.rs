@@ -0,0 +1,437 @@
+//! cpay 指令构造
+
+use ::cpay as program;
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::Result;
+use anchor_spl::token;
+use program::circle::{
+    BurnMessageBody, InitializeMessageTransmitterParams, InitializeTokenMessengerParams, Message,
+    SetBurnLimitsParams,
+};
+use program::state::Role;
+use drift_vaults::state::{Vault, WithdrawUnit};
+
+use super::instruction_builders;
+use crate::pda;
+
+instruction_builders! {
+    initialize(Initialize, Initialize);
+    initialize_fund_account(InitializeFundAccount, InitializeFundAccount);
+    set_allocation(SetAllocation, SetAllocation, weight_bps: u16, cap: u64);
+    register_vault(RegisterVault, RegisterVault);
+    grant_role(UpdateRole, GrantRole, role: Role, account: Pubkey);
+    revoke_role(UpdateRole, RevokeRole, role: Role, account: Pubkey);
+    transfer_admin(TransferAdmin, TransferAdmin, new_admin: Pubkey);
+    accept_admin(AcceptAdmin, AcceptAdmin);
+    pause(Pause, Pause, operations: u8);
+    unpause(Pause, Unpause, operations: u8);
+    deposit(Deposit, Deposit, amount: u64);
+    rebalance(Deposit, Rebalance);
+    request_unstake(RequestUnstake, RequestUnstake, amount: u64, withdraw_unit: WithdrawUnit);
+    cancel_unstake(CancelUnstake, CancelUnstake);
+    complete_unstake(CompleteUnstake, CompleteUnstake);
+    sync_staked_value(SyncStakedValue, SyncStakedValue);
+    deposit_for_burn(
+        DepositForBurn, DepositForBurn,
+        amount: u64, destination_domain: u32, mint_recipient: [u8; 32]
+    );
+    deposit_for_burn_with_caller(
+        DepositForBurn, DepositForBurnWithCaller,
+        amount: u64, destination_domain: u32, mint_recipient: [u8; 32], destination_caller: [u8; 32]
+    );
+    replace_deposit_for_burn(
+        ReplaceDepositForBurn, ReplaceDepositForBurn,
+        new_mint_recipient: [u8; 32], new_destination_caller: [u8; 32]
+    );
+    reclaim_burn_message(ReclaimBurnMessage, ReclaimBurnMessage);
+    set_burn_message_reclaim_delay(
+        SetBurnMessageReclaimDelay, SetBurnMessageReclaimDelay,
+        burn_message_reclaim_delay: i64
+    );
+    receive_message(ReceiveMessage, ReceiveMessage, message: Vec<u8>, signatures: Vec<[u8; 65]>);
+    initialize_message_transmitter(
+        InitializeMessageTransmitter, InitializeMessageTransmitter,
+        params: InitializeMessageTransmitterParams
+    );
+    initialize_token_messenger(
+        InitializeTokenMessenger, InitializeTokenMessenger,
+        params: InitializeTokenMessengerParams
+    );
+    pause_message_transmitter(PauseMessageTransmitter, PauseMessageTransmitter);
+    unpause_message_transmitter(PauseMessageTransmitter, UnpauseMessageTransmitter);
+    transfer_ownership(TransferOwnership, TransferOwnership, new_authority: Pubkey);
+    accept_ownership(AcceptOwnership, AcceptOwnership);
+    transfer_token_messenger_ownership(
+        TransferTokenMessengerOwnership, TransferTokenMessengerOwnership,
+        new_authority: Pubkey
+    );
+    accept_token_messenger_ownership(AcceptTokenMessengerOwnership, AcceptTokenMessengerOwnership);
+    add_remote_token_messenger(
+        AddRemoteTokenMessenger, AddRemoteTokenMessenger,
+        domain: u32, token_messenger: [u8; 32]
+    );
+    remove_remote_token_messenger(RemoveRemoteTokenMessenger, RemoveRemoteTokenMessenger);
+    link_token_pair(LinkTokenPair, LinkTokenPair, remote_domain: u32, remote_token: [u8; 32]);
+    unlink_token_pair(UnlinkTokenPair, UnlinkTokenPair);
+    set_burn_limits(SetBurnLimits, SetBurnLimits, params: SetBurnLimitsParams);
+    enable_attester(EnableAttester, EnableAttester, new_attester: Pubkey);
+    disable_attester(DisableAttester, DisableAttester, attester: Pubkey);
+    set_signature_threshold(SetSignatureThreshold, SetSignatureThreshold, new_signature_threshold: u32);
+}
+
+/// 构造 `initialize` 指令，账户全部自动派生
+pub fn initialize_program(payer: Pubkey) -> Instruction {
+    initialize(
+        program::accounts::Initialize {
+            global_state: pda::cpay::global_state(),
+            access_control: pda::cpay::access_control(),
+            payer,
+            system_program: system_program::ID,
+        },
+        vec![],
+    )
+}
+
+/// 质押相关指令使用的drift金库账户，可由金库账户数据得到
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VaultAccounts {
+    /// Drift金库
+    pub vault: Pubkey,
+    /// 金库在drift中的用户账户
+    pub drift_user: Pubkey,
+    /// 金库在drift中的用户统计账户
+    pub drift_user_stats: Pubkey,
+    /// 金库使用的现货市场
+    pub spot_market_index: u16,
+}
+
+impl VaultAccounts {
+    pub fn new(vault: Pubkey, data: &Vault) -> Self {
+        Self {
+            vault,
+            drift_user: data.user,
+            drift_user_stats: data.user_stats,
+            spot_market_index: data.spot_market_index,
+        }
+    }
+
+    /// 全局状态在该金库的存款人账户
+    pub fn vault_depositor(&self) -> Pubkey {
+        pda::drift_vaults::vault_depositor(&self.vault, &pda::cpay::global_state())
+    }
+}
+
+/// 单个代币的金库，按代币派生 cpay 的金库代币账户、资金账户和分配配置，构造常用指令
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Treasury {
+    /// 金库代币的铸造账户
+    pub mint: Pubkey,
+    /// 金库代币账户
+    pub program_token_account: Pubkey,
+    /// 资金账户
+    pub fund_account: Pubkey,
+    /// 分配配置
+    pub allocation_config: Pubkey,
+}
+
+impl Treasury {
+    pub fn new(mint: Pubkey) -> Self {
+        let program_token_account = pda::cpay::program_token_account(&mint);
+        let fund_account = pda::cpay::fund_account(&program_token_account);
+        Self {
+            mint,
+            program_token_account,
+            fund_account,
+            allocation_config: pda::cpay::allocation_config(&fund_account),
+        }
+    }
+
+    /// 资金账户在drift金库的持仓
+    pub fn position(&self, vault: &Pubkey) -> Pubkey {
+        pda::cpay::position(&self.fund_account, vault)
+    }
+
+    /// 构造 `initialize_fund_account` 指令，payer 必须是管理员
+    pub fn initialize_fund_account(&self, payer: Pubkey) -> Instruction {
+        initialize_fund_account(
+            program::accounts::InitializeFundAccount {
+                global_state: pda::cpay::global_state(),
+                access_control: pda::cpay::access_control(),
+                program_token_account: self.program_token_account,
+                fund_account: self.fund_account,
+                allocation_config: self.allocation_config,
+                mint: self.mint,
+                payer,
+                system_program: system_program::ID,
+                token_program: token::ID,
+            },
+            vec![],
+        )
+    }
+
+    /// 构造 `set_allocation` 指令，admin 必须是管理员
+    pub fn set_allocation(&self, admin: Pubkey, vault: Pubkey, weight_bps: u16, cap: u64) -> Instruction {
+        set_allocation(
+            program::accounts::SetAllocation {
+                access_control: pda::cpay::access_control(),
+                fund_account: self.fund_account,
+                allocation_config: self.allocation_config,
+                vault,
+                position: self.position(&vault),
+                admin,
+                system_program: system_program::ID,
+            },
+            weight_bps,
+            cap,
+            vec![],
+        )
+    }
+
+    /// 构造 `register_vault` 指令，payer 必须是管理员
+    pub fn register_vault(&self, payer: Pubkey, vault: &VaultAccounts) -> Instruction {
+        register_vault(
+            program::accounts::RegisterVault {
+                global_state: pda::cpay::global_state(),
+                access_control: pda::cpay::access_control(),
+                fund_account: self.fund_account,
+                position: self.position(&vault.vault),
+                mint: self.mint,
+                program_token_account: self.program_token_account,
+                vault: vault.vault,
+                drift_spot_market: pda::drift::spot_market(vault.spot_market_index),
+                vault_depositor: vault.vault_depositor(),
+                drift_vaults_program: ::drift_vaults::ID,
+                payer,
+                rent: sysvar::rent::ID,
+                system_program: system_program::ID,
+            },
+            vec![],
+        )
+    }
+
+    fn deposit_accounts(&self, payer: Pubkey, vault: &VaultAccounts) -> program::accounts::Deposit {
+        program::accounts::Deposit {
+            global_state: pda::cpay::global_state(),
+            access_control: pda::cpay::access_control(),
+            fund_account: self.fund_account,
+            allocation_config: self.allocation_config,
+            position: self.position(&vault.vault),
+            mint: self.mint,
+            vault: vault.vault,
+            program_token_account: self.program_token_account,
+            vault_token_account: pda::drift_vaults::vault_token_account(&vault.vault),
+            vault_depositor: vault.vault_depositor(),
+            drift_user_stats: vault.drift_user_stats,
+            drift_user: vault.drift_user,
+            drift_state: pda::drift::state(),
+            drift_spot_market_vault: pda::drift::spot_market_vault(vault.spot_market_index),
+            drift_program: ::drift::ID,
+            drift_vaults_program: ::drift_vaults::ID,
+            token_program: token::ID,
+            payer,
+        }
+    }
+
+    /// 构造 `deposit` 指令，payer 必须是操作员，剩余账户见 [`crate::RemainingAccounts`]
+    pub fn deposit(
+        &self,
+        payer: Pubkey,
+        vault: &VaultAccounts,
+        amount: u64,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        deposit(self.deposit_accounts(payer, vault), amount, remaining_accounts)
+    }
+
+    /// 构造 `rebalance` 指令，payer 必须是操作员
+    pub fn rebalance(
+        &self,
+        payer: Pubkey,
+        vault: &VaultAccounts,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        rebalance(self.deposit_accounts(payer, vault), remaining_accounts)
+    }
+
+    /// 构造 `request_unstake` 指令，payer 必须是操作员
+    pub fn request_unstake(
+        &self,
+        payer: Pubkey,
+        vault: &VaultAccounts,
+        amount: u64,
+        withdraw_unit: WithdrawUnit,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        request_unstake(
+            program::accounts::RequestUnstake {
+                global_state: pda::cpay::global_state(),
+                access_control: pda::cpay::access_control(),
+                fund_account: self.fund_account,
+                position: self.position(&vault.vault),
+                mint: self.mint,
+                program_token_account: self.program_token_account,
+                vault: vault.vault,
+                vault_depositor: vault.vault_depositor(),
+                drift_user_stats: vault.drift_user_stats,
+                drift_user: vault.drift_user,
+                drift_vaults_program: ::drift_vaults::ID,
+                payer,
+            },
+            amount,
+            withdraw_unit,
+            remaining_accounts,
+        )
+    }
+
+    /// 构造 `cancel_unstake` 指令，payer 必须是操作员
+    pub fn cancel_unstake(
+        &self,
+        payer: Pubkey,
+        vault: &VaultAccounts,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        cancel_unstake(
+            program::accounts::CancelUnstake {
+                global_state: pda::cpay::global_state(),
+                access_control: pda::cpay::access_control(),
+                fund_account: self.fund_account,
+                position: self.position(&vault.vault),
+                mint: self.mint,
+                program_token_account: self.program_token_account,
+                vault: vault.vault,
+                vault_depositor: vault.vault_depositor(),
+                drift_user_stats: vault.drift_user_stats,
+                drift_user: vault.drift_user,
+                drift_vaults_program: ::drift_vaults::ID,
+                payer,
+            },
+            remaining_accounts,
+        )
+    }
+
+    /// 构造 `complete_unstake` 指令，payer 必须是操作员
+    pub fn complete_unstake(
+        &self,
+        payer: Pubkey,
+        vault: &VaultAccounts,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        complete_unstake(
+            program::accounts::CompleteUnstake {
+                global_state: pda::cpay::global_state(),
+                access_control: pda::cpay::access_control(),
+                fund_account: self.fund_account,
+                position: self.position(&vault.vault),
+                mint: self.mint,
+                vault: vault.vault,
+                vault_token_account: pda::drift_vaults::vault_token_account(&vault.vault),
+                vault_depositor: vault.vault_depositor(),
+                program_token_account: self.program_token_account,
+                drift_user_stats: vault.drift_user_stats,
+                drift_user: vault.drift_user,
+                drift_state: pda::drift::state(),
+                drift_spot_market_vault: pda::drift::spot_market_vault(vault.spot_market_index),
+                drift_signer: pda::drift::signer(),
+                drift_program: ::drift::ID,
+                drift_vaults_program: ::drift_vaults::ID,
+                token_program: token::ID,
+                payer,
+            },
+            remaining_accounts,
+        )
+    }
+
+    /// 构造 `sync_staked_value` 指令，任何人都可以调用
+    pub fn sync_staked_value(
+        &self,
+        vault: &VaultAccounts,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        sync_staked_value(
+            program::accounts::SyncStakedValue {
+                global_state: pda::cpay::global_state(),
+                fund_account: self.fund_account,
+                position: self.position(&vault.vault),
+                mint: self.mint,
+                program_token_account: self.program_token_account,
+                vault: vault.vault,
+                vault_depositor: vault.vault_depositor(),
+                drift_user: vault.drift_user,
+            },
+            remaining_accounts,
+        )
+    }
+
+    /// 构造 `deposit_for_burn` 或 `deposit_for_burn_with_caller` 指令，authority 必须是操作员
+    ///
+    /// nonce 为消息传输器账户当前的序列号，决定销毁消息账户的地址
+    pub fn deposit_for_burn(
+        &self,
+        authority: Pubkey,
+        nonce: u64,
+        amount: u64,
+        destination_domain: u32,
+        mint_recipient: [u8; 32],
+        destination_caller: Option<[u8; 32]>,
+    ) -> Instruction {
+        let accounts = program::accounts::DepositForBurn {
+            authority,
+            global_state: pda::cpay::global_state(),
+            access_control: pda::cpay::access_control(),
+            program_token_account: self.program_token_account,
+            fund_account: self.fund_account,
+            mint: self.mint,
+            message_transmitter: pda::cpay::message_transmitter(),
+            token_messenger: pda::cpay::token_messenger(&self.mint),
+            remote_token_messenger: pda::cpay::remote_token_messenger(destination_domain),
+            burn_message: pda::cpay::burn_message(nonce),
+            token_program: token::ID,
+            system_program: system_program::ID,
+        };
+        match destination_caller {
+            Some(destination_caller) => deposit_for_burn_with_caller(
+                accounts,
+                amount,
+                destination_domain,
+                mint_recipient,
+                destination_caller,
+                vec![],
+            ),
+            None => deposit_for_burn(accounts, amount, destination_domain, mint_recipient, vec![]),
+        }
+    }
+
+    /// 构造 `receive_message` 指令，authority 必须是中继者，账户按消息内容派生
+    pub fn receive_message(
+        &self,
+        authority: Pubkey,
+        message: Vec<u8>,
+        signatures: Vec<[u8; 65]>,
+    ) -> Result<Instruction> {
+        let parsed = Message::parse(&message)?;
+        let body = BurnMessageBody::parse(&parsed.message_body)?;
+
+        Ok(receive_message(
+            program::accounts::ReceiveMessage {
+                authority,
+                access_control: pda::cpay::access_control(),
+                message_transmitter: pda::cpay::message_transmitter(),
+                remote_token_messenger: pda::cpay::remote_token_messenger(parsed.source_domain),
+                token_pair: pda::cpay::token_pair(parsed.source_domain, &body.burn_token),
+                token_messenger: pda::cpay::token_messenger(&self.mint),
+                used_nonces: pda::cpay::used_nonces(parsed.source_domain, parsed.nonce),
+                global_state: pda::cpay::global_state(),
+                program_token_account: self.program_token_account,
+                fund_account: self.fund_account,
+                mint: self.mint,
+                token_program: token::ID,
+                system_program: system_program::ID,
+            },
+            message,
+            signatures,
+            vec![],
+        ))
+    }
+}
@@ -0,0 +1,71 @@
+//! drift_vaults 指令构造
+
+use ::drift_vaults as program;
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use program::state::WithdrawUnit;
+use program::{
+    AllowlistEntryParams, InitializeTokenizedVaultDepositorParams, UpdateVaultParams,
+    UpdateVaultProtocolParams, VaultParams, VaultWithProtocolParams,
+};
+
+use super::instruction_builders;
+
+instruction_builders! {
+    initialize_vault(InitializeVault, InitializeVault, params: VaultParams);
+    initialize_vault_with_protocol(
+        InitializeVaultWithProtocol, InitializeVaultWithProtocol,
+        params: VaultWithProtocolParams
+    );
+    update_delegate(UpdateDelegate, UpdateDelegate, delegate: Pubkey);
+    update_margin_trading_enabled(UpdateMarginTradingEnabled, UpdateMarginTradingEnabled, enabled: bool);
+    update_vault_protocol(UpdateVaultProtocol, UpdateVaultProtocol, params: UpdateVaultProtocolParams);
+    update_vault(UpdateVault, UpdateVault, params: UpdateVaultParams);
+    add_to_allowlist(AddToAllowlist, AddToAllowlist, params: AllowlistEntryParams);
+    remove_from_allowlist(RemoveFromAllowlist, RemoveFromAllowlist);
+    initialize_vault_depositor(InitializeVaultDepositor, InitializeVaultDepositor);
+    initialize_tokenized_vault_depositor(
+        InitializeTokenizedVaultDepositor, InitializeTokenizedVaultDepositor,
+        params: InitializeTokenizedVaultDepositorParams
+    );
+    tokenize_shares(TokenizeShares, TokenizeShares, amount: u64, unit: WithdrawUnit);
+    redeem_tokens(RedeemTokens, RedeemTokens, tokens_to_burn: u64);
+    deposit(Deposit, Deposit, amount: u64);
+    request_withdraw(RequestWithdraw, RequestWithdraw, withdraw_amount: u64, withdraw_unit: WithdrawUnit);
+    cancel_request_withdraw(CancelWithdrawRequest, CancelRequestWithdraw);
+    withdraw(Withdraw, Withdraw);
+    liquidate(Liquidate, Liquidate);
+    reset_delegate(ResetDelegate, ResetDelegate);
+    manager_deposit(ManagerDeposit, ManagerDeposit, amount: u64);
+    manager_request_withdraw(
+        ManagerRequestWithdraw, ManagerRequestWithdraw,
+        withdraw_amount: u64, withdraw_unit: WithdrawUnit
+    );
+    manger_cancel_withdraw_request(ManagerCancelWithdrawRequest, MangerCancelWithdrawRequest);
+    manager_withdraw(ManagerWithdraw, ManagerWithdraw);
+    apply_profit_share(ApplyProfitShare, ApplyProfitShare);
+    crystallize_fees(CrystallizeFees, CrystallizeFees);
+    apply_rebase(ApplyRebase, ApplyRebase);
+    apply_rebase_tokenized_depositor(ApplyRebaseTokenizedDepositor, ApplyRebaseTokenizedDepositor);
+    force_withdraw(ForceWithdraw, ForceWithdraw);
+    initialize_insurance_fund_stake(
+        InitializeInsuranceFundStake, InitializeInsuranceFundStake,
+        market_index: u16
+    );
+    add_insurance_fund_stake(AddInsuranceFundStake, AddInsuranceFundStake, market_index: u16, amount: u64);
+    request_remove_insurance_fund_stake(
+        RequestRemoveInsuranceFundStake, RequestRemoveInsuranceFundStake,
+        market_index: u16, amount: u64
+    );
+    remove_insurance_fund_stake(RemoveInsuranceFundStake, RemoveInsuranceFundStake, market_index: u16);
+    cancel_request_remove_insurance_fund_stake(
+        RequestRemoveInsuranceFundStake, CancelRequestRemoveInsuranceFundStake,
+        market_index: u16
+    );
+    protocol_request_withdraw(
+        ProtocolRequestWithdraw, ProtocolRequestWithdraw,
+        withdraw_amount: u64, withdraw_unit: WithdrawUnit
+    );
+    protocol_cancel_withdraw_request(ProtocolCancelWithdrawRequest, ProtocolCancelWithdrawRequest);
+    protocol_withdraw(ProtocolWithdraw, ProtocolWithdraw);
+}
@@ -0,0 +1,51 @@
+//! cpay 和 drift_vaults 的指令构造
+//!
+//! 每个链上指令都有一个同名的构造函数，接收 anchor 生成的账户结构和指令参数；
+//! cpay 的常用流程另外提供按代币自动派生账户的 [`cpay::Treasury`]
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+
+pub mod cpay;
+pub mod drift_vaults;
+
+/// 由账户结构、指令参数和剩余账户构造指令
+pub fn build_instruction(
+    program_id: Pubkey,
+    accounts: &impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(remaining_accounts);
+    Instruction {
+        program_id,
+        accounts: account_metas,
+        data: data.data(),
+    }
+}
+
+// 为每个指令生成构造函数：指令名(账户结构, 指令参数结构, 参数...)
+macro_rules! instruction_builders {
+    ($($name:ident($accounts:ident, $data:ident $(, $arg:ident: $ty:ty)*);)*) => {
+        $(
+            #[doc = concat!("构造 `", stringify!($name), "` 指令")]
+            #[allow(clippy::too_many_arguments)]
+            pub fn $name(
+                accounts: program::accounts::$accounts,
+                $($arg: $ty,)*
+                remaining_accounts: Vec<AccountMeta>,
+            ) -> Instruction {
+                super::build_instruction(
+                    program::ID,
+                    &accounts,
+                    program::instruction::$data { $($arg),* },
+                    remaining_accounts,
+                )
+            }
+        )*
+    };
+}
+
+pub(crate) use instruction_builders;
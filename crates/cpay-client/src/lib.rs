@@ -0,0 +1,14 @@
+//! cpay 和 drift_vaults 的链下客户端
+//!
+//! - [`pda`]：按链上程序的种子派生账户地址
+//! - [`instructions`]：构造两个程序的全部指令
+//! - [`accounts`]：解析链上账户数据
+//! - [`RemainingAccounts`]：按 drift 的约定组装剩余账户
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+pub mod remaining_accounts;
+mod tests;
+
+pub use remaining_accounts::RemainingAccounts;
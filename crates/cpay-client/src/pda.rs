@@ -0,0 +1,227 @@
+//! cpay、drift_vaults 和 drift 账户的PDA派生，种子与链上程序的账户约束保持一致
+
+use anchor_lang::prelude::Pubkey;
+
+fn find(seeds: &[&[u8]], program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(seeds, program_id).0
+}
+
+/// cpay 程序的PDA
+pub mod cpay {
+    use super::*;
+
+    /// 全局状态账户，所有金库代币账户和drift金库存款人的authority
+    pub fn global_state() -> Pubkey {
+        find(&[b"global_state"], &::cpay::ID)
+    }
+
+    /// 权限控制账户
+    pub fn access_control() -> Pubkey {
+        find(&[b"access_control"], &::cpay::ID)
+    }
+
+    /// 代币对应的金库代币账户
+    pub fn program_token_account(mint: &Pubkey) -> Pubkey {
+        find(&[b"program_token_account", mint.as_ref()], &::cpay::ID)
+    }
+
+    /// 金库代币账户对应的资金账户
+    pub fn fund_account(program_token_account: &Pubkey) -> Pubkey {
+        find(&[b"fund_account", program_token_account.as_ref()], &::cpay::ID)
+    }
+
+    /// 代币对应的资金账户
+    pub fn fund_account_for_mint(mint: &Pubkey) -> Pubkey {
+        fund_account(&program_token_account(mint))
+    }
+
+    /// 资金账户的分配配置
+    pub fn allocation_config(fund_account: &Pubkey) -> Pubkey {
+        find(&[b"allocation_config", fund_account.as_ref()], &::cpay::ID)
+    }
+
+    /// 资金账户在drift金库的持仓
+    pub fn position(fund_account: &Pubkey, vault: &Pubkey) -> Pubkey {
+        find(&[b"position", fund_account.as_ref(), vault.as_ref()], &::cpay::ID)
+    }
+
+    /// 消息传输器账户
+    pub fn message_transmitter() -> Pubkey {
+        find(&[b"message_transmitter"], &::cpay::ID)
+    }
+
+    /// 代币对应的代币消息传输器账户
+    pub fn token_messenger(mint: &Pubkey) -> Pubkey {
+        find(&[b"token_messenger", mint.as_ref()], &::cpay::ID)
+    }
+
+    /// 远端链域对应的远端代币消息传输器账户
+    pub fn remote_token_messenger(domain: u32) -> Pubkey {
+        find(&[b"remote_token_messenger", &domain.to_le_bytes()], &::cpay::ID)
+    }
+
+    /// (远端链域, 远端代币) 对应的代币对账户
+    pub fn token_pair(remote_domain: u32, remote_token: &[u8; 32]) -> Pubkey {
+        find(
+            &[b"token_pair", &remote_domain.to_le_bytes(), remote_token],
+            &::cpay::ID,
+        )
+    }
+
+    /// 序列号对应的销毁消息账户，序列号取自消息传输器账户，本链域所有代币共用
+    pub fn burn_message(nonce: u64) -> Pubkey {
+        find(&[b"burn_message", &nonce.to_le_bytes()], &::cpay::ID)
+    }
+
+    /// (源链域, 序列号) 对应的已使用序列号账户
+    pub fn used_nonces(source_domain: u32, nonce: u64) -> Pubkey {
+        let first_nonce = ::cpay::circle::UsedNonces::first_nonce(nonce);
+        find(
+            &[
+                b"used_nonces",
+                &source_domain.to_le_bytes(),
+                &first_nonce.to_le_bytes(),
+            ],
+            &::cpay::ID,
+        )
+    }
+}
+
+/// drift_vaults 程序的PDA
+pub mod drift_vaults {
+    use super::*;
+
+    /// 按名称派生金库账户，名称可通过 encode_name 编码
+    pub fn vault(name: &[u8; 32]) -> Pubkey {
+        find(&[b"vault", name], &::drift_vaults::ID)
+    }
+
+    /// 金库的代币账户
+    pub fn vault_token_account(vault: &Pubkey) -> Pubkey {
+        find(&[b"vault_token_account", vault.as_ref()], &::drift_vaults::ID)
+    }
+
+    /// 金库在保险基金中使用的代币账户
+    pub fn insurance_fund_token_account(vault: &Pubkey, market_index: u16) -> Pubkey {
+        find(
+            &[
+                b"vault_token_account",
+                vault.as_ref(),
+                &market_index.to_le_bytes(),
+            ],
+            &::drift_vaults::ID,
+        )
+    }
+
+    /// 金库存款人账户
+    pub fn vault_depositor(vault: &Pubkey, authority: &Pubkey) -> Pubkey {
+        find(
+            &[b"vault_depositor", vault.as_ref(), authority.as_ref()],
+            &::drift_vaults::ID,
+        )
+    }
+
+    /// 许可制金库的白名单条目
+    pub fn allowlist_entry(vault: &Pubkey, authority: &Pubkey) -> Pubkey {
+        find(
+            &[b"allowlist_entry", vault.as_ref(), authority.as_ref()],
+            &::drift_vaults::ID,
+        )
+    }
+
+    /// 金库的协议账户
+    pub fn vault_protocol(vault: &Pubkey) -> Pubkey {
+        find(&[b"vault_protocol", vault.as_ref()], &::drift_vaults::ID)
+    }
+
+    /// 金库在指定份额基数下的代币化存款人账户
+    pub fn tokenized_vault_depositor(vault: &Pubkey, shares_base: u32) -> Pubkey {
+        find(
+            &[
+                b"tokenized_vault_depositor",
+                vault.as_ref(),
+                shares_base.to_string().as_bytes(),
+            ],
+            &::drift_vaults::ID,
+        )
+    }
+
+    /// 代币化存款人的份额代币铸造账户
+    pub fn tokenized_vault_mint(vault: &Pubkey, shares_base: u32) -> Pubkey {
+        find(
+            &[b"mint", vault.as_ref(), shares_base.to_string().as_bytes()],
+            &::drift_vaults::ID,
+        )
+    }
+
+    /// 把金库名称编码为32字节并以空格补齐，与 ts sdk 的 encodeName 一致，超过32字节时返回 None
+    pub fn encode_name(name: &str) -> Option<[u8; 32]> {
+        let bytes = name.as_bytes();
+        if bytes.len() > 32 {
+            return None;
+        }
+        let mut encoded = [b' '; 32];
+        encoded[..bytes.len()].copy_from_slice(bytes);
+        Some(encoded)
+    }
+}
+
+/// drift 程序的PDA
+pub mod drift {
+    use super::*;
+
+    /// 状态账户
+    pub fn state() -> Pubkey {
+        find(&[b"drift_state"], &::drift::ID)
+    }
+
+    /// 签名者账户
+    pub fn signer() -> Pubkey {
+        find(&[b"drift_signer"], &::drift::ID)
+    }
+
+    /// 用户账户
+    pub fn user(authority: &Pubkey, sub_account_id: u16) -> Pubkey {
+        find(
+            &[b"user", authority.as_ref(), &sub_account_id.to_le_bytes()],
+            &::drift::ID,
+        )
+    }
+
+    /// 用户统计账户
+    pub fn user_stats(authority: &Pubkey) -> Pubkey {
+        find(&[b"user_stats", authority.as_ref()], &::drift::ID)
+    }
+
+    /// 现货市场账户
+    pub fn spot_market(market_index: u16) -> Pubkey {
+        find(&[b"spot_market", &market_index.to_le_bytes()], &::drift::ID)
+    }
+
+    /// 现货市场金库账户
+    pub fn spot_market_vault(market_index: u16) -> Pubkey {
+        find(&[b"spot_market_vault", &market_index.to_le_bytes()], &::drift::ID)
+    }
+
+    /// 永续市场账户
+    pub fn perp_market(market_index: u16) -> Pubkey {
+        find(&[b"perp_market", &market_index.to_le_bytes()], &::drift::ID)
+    }
+
+    /// 保险基金金库账户
+    pub fn insurance_fund_vault(market_index: u16) -> Pubkey {
+        find(&[b"insurance_fund_vault", &market_index.to_le_bytes()], &::drift::ID)
+    }
+
+    /// 用户在保险基金中的质押账户，金库的质押账户以金库为authority
+    pub fn insurance_fund_stake(authority: &Pubkey, market_index: u16) -> Pubkey {
+        find(
+            &[
+                b"insurance_fund_stake",
+                authority.as_ref(),
+                &market_index.to_le_bytes(),
+            ],
+            &::drift::ID,
+        )
+    }
+}
@@ -0,0 +1,91 @@
+//! drift_vaults 指令需要的剩余账户
+//!
+//! drift 按 预言机 -> 现货市场 -> 永续市场 的顺序读取剩余账户，金库有协议账户时协议账户必须放在最后
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use drift::state::user::User;
+
+use crate::pda;
+
+/// 剩余账户构造器
+#[derive(Clone, Debug, Default)]
+pub struct RemainingAccounts {
+    oracles: BTreeSet<Pubkey>,
+    // 市场索引 -> 是否可写
+    spot_markets: BTreeMap<u16, bool>,
+    perp_markets: BTreeSet<u16>,
+    vault_protocol: Option<Pubkey>,
+}
+
+impl RemainingAccounts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加预言机账户
+    pub fn oracle(mut self, oracle: Pubkey) -> Self {
+        self.oracles.insert(oracle);
+        self
+    }
+
+    /// 添加现货市场，存取款涉及的现货市场需要可写
+    pub fn spot_market(mut self, market_index: u16, writable: bool) -> Self {
+        let entry = self.spot_markets.entry(market_index).or_insert(false);
+        *entry |= writable;
+        self
+    }
+
+    /// 添加永续市场
+    pub fn perp_market(mut self, market_index: u16) -> Self {
+        self.perp_markets.insert(market_index);
+        self
+    }
+
+    /// 添加金库的协议账户
+    pub fn vault_protocol(mut self, vault: &Pubkey) -> Self {
+        self.vault_protocol = Some(pda::drift_vaults::vault_protocol(vault));
+        self
+    }
+
+    /// 添加drift用户所有持仓涉及的现货和永续市场，对应的预言机需要另外添加
+    pub fn user_positions(mut self, user: &User) -> Self {
+        for position in user.spot_positions.iter().filter(|p| !p.is_available()) {
+            self = self.spot_market(position.market_index, false);
+        }
+        for position in user.perp_positions.iter().filter(|p| !p.is_available()) {
+            self = self.perp_market(position.market_index);
+        }
+        self
+    }
+
+    /// 按drift的读取顺序生成账户列表
+    pub fn build(self) -> Vec<AccountMeta> {
+        let oracles = self
+            .oracles
+            .into_iter()
+            .map(|oracle| AccountMeta::new_readonly(oracle, false));
+        let spot_markets = self.spot_markets.into_iter().map(|(market_index, writable)| {
+            let address = pda::drift::spot_market(market_index);
+            if writable {
+                AccountMeta::new(address, false)
+            } else {
+                AccountMeta::new_readonly(address, false)
+            }
+        });
+        let perp_markets = self
+            .perp_markets
+            .into_iter()
+            .map(|market_index| AccountMeta::new_readonly(pda::drift::perp_market(market_index), false));
+        let vault_protocol = self
+            .vault_protocol
+            .map(|vault_protocol| AccountMeta::new(vault_protocol, false));
+
+        oracles
+            .chain(spot_markets)
+            .chain(perp_markets)
+            .chain(vault_protocol)
+            .collect()
+    }
+}
@@ -0,0 +1,351 @@
+#[cfg(test)]
+mod pda_tests {
+    use crate::instructions::cpay::{Treasury, VaultAccounts};
+    use crate::pda;
+    use anchor_lang::prelude::Pubkey;
+    use cpay::circle::UsedNonces;
+
+    #[test]
+    fn test_fund_account_derivation() {
+        let mint = Pubkey::new_unique();
+        let program_token_account = pda::cpay::program_token_account(&mint);
+        let fund_account = pda::cpay::fund_account(&program_token_account);
+        assert_eq!(pda::cpay::fund_account_for_mint(&mint), fund_account);
+
+        let treasury = Treasury::new(mint);
+        assert_eq!(treasury.program_token_account, program_token_account);
+        assert_eq!(treasury.fund_account, fund_account);
+        assert_eq!(
+            treasury.allocation_config,
+            pda::cpay::allocation_config(&fund_account)
+        );
+
+        // 不同代币的资金账户互不相同
+        assert_ne!(
+            pda::cpay::fund_account_for_mint(&Pubkey::new_unique()),
+            fund_account
+        );
+    }
+
+    #[test]
+    fn test_burn_message_and_used_nonces() {
+        assert_ne!(pda::cpay::burn_message(1), pda::cpay::burn_message(2));
+
+        // 同一序列号区间共用一个已使用序列号账户
+        assert_eq!(pda::cpay::used_nonces(0, 1), pda::cpay::used_nonces(0, 2));
+        assert_ne!(
+            pda::cpay::used_nonces(0, 1),
+            pda::cpay::used_nonces(0, UsedNonces::MAX_NONCES)
+        );
+        assert_ne!(pda::cpay::used_nonces(0, 1), pda::cpay::used_nonces(1, 1));
+    }
+
+    #[test]
+    fn test_encode_name() {
+        let name = pda::drift_vaults::encode_name("cpay vault").unwrap();
+        assert_eq!(&name[..10], b"cpay vault");
+        assert!(name[10..].iter().all(|b| *b == b' '));
+
+        assert!(pda::drift_vaults::encode_name(&"a".repeat(32)).is_some());
+        assert!(pda::drift_vaults::encode_name(&"a".repeat(33)).is_none());
+
+        let other = pda::drift_vaults::encode_name("other vault").unwrap();
+        assert_ne!(
+            pda::drift_vaults::vault(&name),
+            pda::drift_vaults::vault(&other)
+        );
+    }
+
+    #[test]
+    fn test_vault_depositor_is_owned_by_global_state() {
+        let vault = VaultAccounts {
+            vault: Pubkey::new_unique(),
+            drift_user: Pubkey::new_unique(),
+            drift_user_stats: Pubkey::new_unique(),
+            spot_market_index: 0,
+        };
+        assert_eq!(
+            vault.vault_depositor(),
+            pda::drift_vaults::vault_depositor(&vault.vault, &pda::cpay::global_state())
+        );
+    }
+
+    #[test]
+    fn test_tokenized_vault_depositor() {
+        let vault = Pubkey::new_unique();
+        assert_ne!(
+            pda::drift_vaults::tokenized_vault_depositor(&vault, 0),
+            pda::drift_vaults::tokenized_vault_depositor(&vault, 1)
+        );
+        assert_ne!(
+            pda::drift_vaults::tokenized_vault_mint(&vault, 0),
+            pda::drift_vaults::tokenized_vault_depositor(&vault, 0)
+        );
+    }
+}
+
+#[cfg(test)]
+mod instruction_tests {
+    use crate::instructions::cpay::{Treasury, VaultAccounts};
+    use crate::instructions::{cpay, drift_vaults};
+    use crate::pda;
+    use anchor_lang::prelude::{AccountMeta, Pubkey};
+    use anchor_lang::solana_program::hash::hash;
+    use anchor_lang::AnchorDeserialize;
+    use ::cpay::circle::{BurnMessageBody, Message};
+
+    fn sighash(name: &str) -> [u8; 8] {
+        let mut sighash = [0u8; 8];
+        sighash.copy_from_slice(&hash(format!("global:{}", name).as_bytes()).to_bytes()[..8]);
+        sighash
+    }
+
+    fn vault_accounts() -> VaultAccounts {
+        VaultAccounts {
+            vault: Pubkey::new_unique(),
+            drift_user: Pubkey::new_unique(),
+            drift_user_stats: Pubkey::new_unique(),
+            spot_market_index: 0,
+        }
+    }
+
+    #[test]
+    fn test_deposit() {
+        let treasury = Treasury::new(Pubkey::new_unique());
+        let vault = vault_accounts();
+        let payer = Pubkey::new_unique();
+        let oracle = AccountMeta::new_readonly(Pubkey::new_unique(), false);
+
+        let ix = treasury.deposit(payer, &vault, 1_000_000, vec![oracle.clone()]);
+        assert_eq!(ix.program_id, ::cpay::ID);
+        assert_eq!(ix.data[..8], sighash("deposit"));
+        assert_eq!(u64::try_from_slice(&ix.data[8..]).unwrap(), 1_000_000);
+
+        // 账户顺序与账户结构一致，剩余账户在最后
+        assert_eq!(ix.accounts.len(), 19);
+        assert_eq!(ix.accounts[2].pubkey, treasury.fund_account);
+        assert!(ix.accounts[2].is_writable);
+        assert_eq!(ix.accounts[4].pubkey, treasury.position(&vault.vault));
+        assert_eq!(ix.accounts[9].pubkey, vault.vault_depositor());
+        assert_eq!(ix.accounts[17], AccountMeta::new(payer, true));
+        assert_eq!(ix.accounts[18], oracle);
+
+        let ix = treasury.rebalance(payer, &vault, vec![]);
+        assert_eq!(ix.data, sighash("rebalance"));
+        assert_eq!(ix.accounts.len(), 18);
+    }
+
+    #[test]
+    fn test_deposit_for_burn() {
+        let treasury = Treasury::new(Pubkey::new_unique());
+        let authority = Pubkey::new_unique();
+
+        let ix = treasury.deposit_for_burn(authority, 7, 100, 0, [1; 32], None);
+        assert_eq!(ix.data[..8], sighash("deposit_for_burn"));
+        assert_eq!(ix.accounts[0], AccountMeta::new(authority, true));
+        assert_eq!(ix.accounts[8].pubkey, pda::cpay::remote_token_messenger(0));
+        assert_eq!(ix.accounts[9].pubkey, pda::cpay::burn_message(7));
+
+        let ix = treasury.deposit_for_burn(authority, 7, 100, 0, [1; 32], Some([2; 32]));
+        assert_eq!(ix.data[..8], sighash("deposit_for_burn_with_caller"));
+        assert_eq!(ix.data[ix.data.len() - 32..], [2; 32]);
+    }
+
+    #[test]
+    fn test_receive_message() {
+        let treasury = Treasury::new(Pubkey::new_unique());
+        let message = Message {
+            version: 0,
+            source_domain: 3,
+            destination_domain: 5,
+            nonce: 42,
+            sender: [4; 32],
+            recipient: [5; 32],
+            destination_caller: [0; 32],
+            message_body: BurnMessageBody {
+                version: 0,
+                burn_token: [1; 32],
+                mint_recipient: [2; 32],
+                amount: 1_000_000,
+                message_sender: [3; 32],
+            }
+            .serialize(),
+        }
+        .serialize();
+
+        let ix = treasury
+            .receive_message(Pubkey::new_unique(), message.clone(), vec![[0; 65]])
+            .unwrap();
+        assert_eq!(ix.data[..8], sighash("receive_message"));
+        assert_eq!(ix.accounts[3].pubkey, pda::cpay::remote_token_messenger(3));
+        assert_eq!(ix.accounts[4].pubkey, pda::cpay::token_pair(3, &[1; 32]));
+        assert_eq!(
+            ix.accounts[5].pubkey,
+            pda::cpay::token_messenger(&treasury.mint)
+        );
+        assert_eq!(ix.accounts[6].pubkey, pda::cpay::used_nonces(3, 42));
+
+        assert!(treasury
+            .receive_message(Pubkey::new_unique(), message[..10].to_vec(), vec![])
+            .is_err());
+    }
+
+    #[test]
+    fn test_drift_vaults_instruction() {
+        let accounts = ::drift_vaults::accounts::InitializeVaultDepositor {
+            vault: Pubkey::new_unique(),
+            vault_depositor: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            rent: anchor_lang::solana_program::sysvar::rent::ID,
+            system_program: anchor_lang::solana_program::system_program::ID,
+            allowlist_entry: None,
+        };
+        let ix = drift_vaults::initialize_vault_depositor(accounts, vec![]);
+        assert_eq!(ix.program_id, ::drift_vaults::ID);
+        assert_eq!(ix.data, sighash("initialize_vault_depositor"));
+        // 未传入的可选账户以程序ID占位
+        assert_eq!(ix.accounts.len(), 7);
+        assert_eq!(ix.accounts[6].pubkey, ::drift_vaults::ID);
+
+        // 指令名与账户结构名不一致的指令
+        let ix = drift_vaults::manger_cancel_withdraw_request(
+            ::drift_vaults::accounts::ManagerCancelWithdrawRequest {
+                vault: Pubkey::new_unique(),
+                manager: Pubkey::new_unique(),
+                drift_user_stats: Pubkey::new_unique(),
+                drift_user: Pubkey::new_unique(),
+            },
+            vec![],
+        );
+        assert_eq!(ix.data, sighash("manger_cancel_withdraw_request"));
+
+        let ix = cpay::set_signature_threshold(
+            ::cpay::accounts::SetSignatureThreshold {
+                authority: Pubkey::new_unique(),
+                message_transmitter: pda::cpay::message_transmitter(),
+            },
+            2,
+            vec![],
+        );
+        assert_eq!(ix.data[..8], sighash("set_signature_threshold"));
+        assert_eq!(ix.data[8..], 2u32.to_le_bytes());
+    }
+}
+
+#[cfg(test)]
+mod remaining_accounts_tests {
+    use crate::{pda, RemainingAccounts};
+    use anchor_lang::prelude::{AccountMeta, Pubkey};
+    use drift::state::user::User;
+
+    #[test]
+    fn test_order() {
+        let oracle = Pubkey::new_unique();
+        let vault = Pubkey::new_unique();
+        let accounts = RemainingAccounts::new()
+            .vault_protocol(&vault)
+            .perp_market(0)
+            .spot_market(1, false)
+            .oracle(oracle)
+            .spot_market(0, true)
+            .spot_market(0, false)
+            .build();
+
+        assert_eq!(
+            accounts,
+            vec![
+                AccountMeta::new_readonly(oracle, false),
+                AccountMeta::new(pda::drift::spot_market(0), false),
+                AccountMeta::new_readonly(pda::drift::spot_market(1), false),
+                AccountMeta::new_readonly(pda::drift::perp_market(0), false),
+                AccountMeta::new(pda::drift_vaults::vault_protocol(&vault), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_user_positions() {
+        let mut user = User::default();
+        user.spot_positions[0].market_index = 0;
+        user.spot_positions[0].scaled_balance = 1;
+        user.spot_positions[1].market_index = 3;
+        user.spot_positions[1].scaled_balance = 1;
+        user.perp_positions[0].market_index = 2;
+        user.perp_positions[0].base_asset_amount = 1;
+
+        let accounts = RemainingAccounts::new()
+            .spot_market(0, true)
+            .user_positions(&user)
+            .build();
+
+        assert_eq!(
+            accounts,
+            vec![
+                AccountMeta::new(pda::drift::spot_market(0), false),
+                AccountMeta::new_readonly(pda::drift::spot_market(3), false),
+                AccountMeta::new_readonly(pda::drift::perp_market(2), false),
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod accounts_tests {
+    use crate::accounts::{
+        deserialize, deserialize_zero_copy, serialize_zero_copy, FundAccount, GlobalState, Vault,
+        VaultDepositor,
+    };
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::AccountSerialize;
+    use bytemuck::Zeroable;
+
+    #[test]
+    fn test_deserialize() {
+        let fund_account = FundAccount {
+            idle_amount: 1,
+            staked_amount: 2,
+            unstaking_amount: 3,
+            sent_amount: 4,
+            total_received_amount: 5,
+            staked_value: 6,
+            unrealized_pnl: -7,
+            realized_yield: 8,
+            last_update_time: 9,
+            authority: Pubkey::new_unique(),
+        };
+        let mut data = vec![];
+        fund_account.try_serialize(&mut data).unwrap();
+
+        let parsed: FundAccount = deserialize(&data).unwrap();
+        assert_eq!(parsed.unrealized_pnl, -7);
+        assert_eq!(parsed.authority, fund_account.authority);
+
+        // 鉴别码不匹配
+        assert!(deserialize::<GlobalState>(&data).is_err());
+        assert!(deserialize::<FundAccount>(&data[..4]).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_zero_copy() {
+        let mut vault = Vault::zeroed();
+        vault.pubkey = Pubkey::new_unique();
+        vault.total_shares = 1_000;
+        vault.spot_market_index = 3;
+
+        let data = serialize_zero_copy(&vault);
+        let parsed: Vault = deserialize_zero_copy(&data).unwrap();
+        assert_eq!(parsed.pubkey, vault.pubkey);
+        assert_eq!(parsed.total_shares, 1_000);
+        assert_eq!(parsed.spot_market_index, 3);
+
+        // 账户数据可以比结构体长（预留空间），但不能更短
+        let mut padded = data.clone();
+        padded.extend_from_slice(&[0; 16]);
+        assert!(deserialize_zero_copy::<Vault>(&padded).is_ok());
+        assert!(deserialize_zero_copy::<Vault>(&data[..data.len() - 1]).is_err());
+
+        assert!(deserialize_zero_copy::<VaultDepositor>(&data).is_err());
+        assert!(deserialize_zero_copy::<Vault>(&data[..4]).is_err());
+    }
+}
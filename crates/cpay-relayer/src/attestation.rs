@@ -0,0 +1,148 @@
+//! 验证人签名来源
+
+use std::path::Path;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::keccak;
+use anyhow::{anyhow, bail, Context, Result};
+use cpay::circle::SIGNATURE_LENGTH;
+use libsecp256k1::{sign, Message, PublicKey, SecretKey};
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+pub type Signatures = Vec<[u8; SIGNATURE_LENGTH]>;
+
+/// 按消息获取验证人签名
+pub trait AttestationSource {
+    /// 返回满足签名阈值、按验证人地址升序排列的签名，尚未签名时返回 None
+    fn attestation(&self, message: &[u8]) -> Result<Option<Signatures>>;
+}
+
+impl<T: AttestationSource + ?Sized> AttestationSource for Box<T> {
+    fn attestation(&self, message: &[u8]) -> Result<Option<Signatures>> {
+        (**self).attestation(message)
+    }
+}
+
+/// Circle 的签名服务
+pub struct HttpAttestationSource {
+    client: reqwest::blocking::Client,
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct AttestationResponse {
+    status: String,
+    attestation: Option<String>,
+}
+
+impl HttpAttestationSource {
+    pub fn new(url: &str) -> Self {
+        Self {
+            client: reqwest::blocking::Client::new(),
+            url: url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+impl AttestationSource for HttpAttestationSource {
+    fn attestation(&self, message: &[u8]) -> Result<Option<Signatures>> {
+        let message_hash = keccak::hash(message).to_bytes();
+        let url = format!(
+            "{}/v1/attestations/0x{}",
+            self.url,
+            hex::encode(message_hash)
+        );
+        let response = self.client.get(&url).send()?;
+        // 签名服务尚未收到消息
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let response: AttestationResponse = response.error_for_status()?.json()?;
+        match (response.status.as_str(), response.attestation) {
+            ("complete", Some(attestation)) => parse_attestation(&attestation).map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// 解析签名服务返回的签名，多个65字节签名直接拼接后以十六进制编码
+pub fn parse_attestation(attestation: &str) -> Result<Signatures> {
+    let bytes = hex::decode(attestation.trim_start_matches("0x"))
+        .context("attestation is not valid hex")?;
+    if bytes.is_empty() || bytes.len() % SIGNATURE_LENGTH != 0 {
+        bail!("invalid attestation length {}", bytes.len());
+    }
+    Ok(bytes
+        .chunks_exact(SIGNATURE_LENGTH)
+        .map(|chunk| chunk.try_into().unwrap())
+        .collect())
+}
+
+/// 本地验证人，用本地私钥直接签名，用于没有签名服务的本地验证器测试
+///
+/// 目标链的消息传输器需要启用 [`LocalAttester::attesters`] 中的地址，并把签名阈值设为私钥数量
+pub struct LocalAttester {
+    // 按验证人地址升序排列
+    secret_keys: Vec<SecretKey>,
+}
+
+impl LocalAttester {
+    pub fn new(mut secret_keys: Vec<SecretKey>) -> Self {
+        secret_keys.sort_by_key(attester_address);
+        Self { secret_keys }
+    }
+
+    /// 从JSON文件读取私钥，文件内容为十六进制私钥数组
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let data = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read attester keys {}", path.display()))?;
+        let keys: Vec<String> = serde_json::from_str(&data)
+            .with_context(|| format!("failed to parse attester keys {}", path.display()))?;
+        let secret_keys = keys
+            .iter()
+            .map(|key| {
+                let bytes = hex::decode(key.trim_start_matches("0x"))?;
+                let bytes: [u8; 32] = bytes
+                    .try_into()
+                    .map_err(|_| anyhow!("attester key must be 32 bytes"))?;
+                SecretKey::parse(&bytes).map_err(|e| anyhow!("invalid attester key: {:?}", e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if secret_keys.is_empty() {
+            bail!("no attester keys in {}", path.display());
+        }
+        Ok(Self::new(secret_keys))
+    }
+
+    /// 验证人地址（以太坊地址左侧补零到32字节），与链上 enable_attester 的参数一致
+    pub fn attesters(&self) -> Vec<Pubkey> {
+        self.secret_keys.iter().map(attester_address).collect()
+    }
+}
+
+impl AttestationSource for LocalAttester {
+    fn attestation(&self, message: &[u8]) -> Result<Option<Signatures>> {
+        let digest = keccak::hash(message).to_bytes();
+        let message = Message::parse(&digest);
+        Ok(Some(
+            self.secret_keys
+                .iter()
+                .map(|key| {
+                    let (signature, recovery_id) = sign(&message, key);
+                    let mut out = [0u8; SIGNATURE_LENGTH];
+                    out[..64].copy_from_slice(&signature.serialize());
+                    out[64] = recovery_id.serialize() + 27;
+                    out
+                })
+                .collect(),
+        ))
+    }
+}
+
+fn attester_address(secret_key: &SecretKey) -> Pubkey {
+    let pubkey = PublicKey::from_secret_key(secret_key).serialize();
+    let mut address = keccak::hash(&pubkey[1..]).to_bytes();
+    address[..12].fill(0);
+    Pubkey::new_from_array(address)
+}
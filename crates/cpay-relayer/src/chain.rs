@@ -0,0 +1,228 @@
+//! 源链和目标链访问
+//!
+//! 源链上 cpay 的 deposit_for_burn / replace_deposit_for_burn 在发出 CrossChainFundSent 的同时发出携带完整消息的
+//! MessageSent 事件，中继器按交易日志读取该事件；目标链上按消息派生账户并提交 receive_message
+
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use anyhow::{anyhow, Context, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use cpay::circle::{BurnMessageBody, Message, MessageSent};
+use cpay_client::accounts::{deserialize, MessageTransmitter, TokenPair, UsedNonces};
+use cpay_client::instructions::cpay::Treasury;
+use cpay_client::pda;
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::attestation::Signatures;
+
+/// 源链交易中发出的跨链消息
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SentMessage {
+    pub signature: String,
+    pub message: Vec<u8>,
+}
+
+// anchor 的错误类型未实现 std::error::Error，转换为文本
+pub(crate) fn anchor_error(error: anchor_lang::error::Error) -> anyhow::Error {
+    anyhow!("{}", error)
+}
+
+pub trait Source {
+    /// 读取比 until 更新的交易中发出的消息，按交易先后排列，同时返回读取到的最新交易
+    fn messages_since(&self, until: Option<&str>) -> Result<(Vec<SentMessage>, Option<String>)>;
+}
+
+pub trait Destination {
+    /// 目标链的本链域ID
+    fn local_domain(&self) -> Result<u32>;
+    /// 消息是否已被接收
+    fn is_received(&self, source_domain: u32, nonce: u64) -> Result<bool>;
+    /// 提交 receive_message，返回交易签名
+    fn receive_message(&self, message: &[u8], signatures: Signatures) -> Result<String>;
+}
+
+/// 从交易日志中解析指定程序发出的 MessageSent 事件，忽略其他程序（包括被 cpay 调用的程序）的事件
+pub fn parse_message_sent(program_id: &Pubkey, logs: &[String]) -> Vec<Vec<u8>> {
+    let program_id = program_id.to_string();
+    // 调用栈，记录每一层是否是目标程序
+    let mut stack: Vec<bool> = vec![];
+    let mut messages = vec![];
+
+    for log in logs {
+        if let Some(data) = log.strip_prefix("Program data: ") {
+            if stack.last() != Some(&true) {
+                continue;
+            }
+            let Ok(data) = STANDARD.decode(data) else {
+                continue;
+            };
+            if data.len() < 8 || data[..8] != MessageSent::DISCRIMINATOR {
+                continue;
+            }
+            if let Ok(event) = MessageSent::try_from_slice(&data[8..]) {
+                messages.push(event.message);
+            }
+            continue;
+        }
+
+        let mut parts = log.split_whitespace();
+        if parts.next() != Some("Program") {
+            continue;
+        }
+        let (Some(id), Some(action)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        match action {
+            "invoke" => stack.push(id == program_id),
+            "success" | "failed:" => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+    messages
+}
+
+pub struct RpcSource {
+    client: RpcClient,
+    program_id: Pubkey,
+}
+
+impl RpcSource {
+    pub fn new(client: RpcClient, program_id: Pubkey) -> Self {
+        Self { client, program_id }
+    }
+}
+
+impl Source for RpcSource {
+    fn messages_since(&self, until: Option<&str>) -> Result<(Vec<SentMessage>, Option<String>)> {
+        let until_signature = until.map(Signature::from_str).transpose()?;
+
+        // 按页从新到旧读取，直到 until
+        let mut statuses = vec![];
+        let mut before = None;
+        loop {
+            let page = self.client.get_signatures_for_address_with_config(
+                &self.program_id,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until: until_signature,
+                    limit: None,
+                    commitment: Some(CommitmentConfig::confirmed()),
+                },
+            )?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            before = Some(Signature::from_str(&last.signature)?);
+            statuses.extend(page);
+        }
+
+        let latest = statuses
+            .first()
+            .map(|status| status.signature.clone())
+            .or_else(|| until.map(str::to_string));
+
+        let mut messages = vec![];
+        for status in statuses.iter().rev().filter(|status| status.err.is_none()) {
+            let transaction = self.client.get_transaction_with_config(
+                &Signature::from_str(&status.signature)?,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Json),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )?;
+            let logs: Option<Vec<String>> = transaction
+                .transaction
+                .meta
+                .and_then(|meta| meta.log_messages.into());
+            messages.extend(
+                parse_message_sent(&self.program_id, &logs.unwrap_or_default())
+                    .into_iter()
+                    .map(|message| SentMessage {
+                        signature: status.signature.clone(),
+                        message,
+                    }),
+            );
+        }
+        Ok((messages, latest))
+    }
+}
+
+pub struct RpcDestination {
+    client: RpcClient,
+    relayer: Keypair,
+}
+
+impl RpcDestination {
+    pub fn new(client: RpcClient, relayer: Keypair) -> Self {
+        Self { client, relayer }
+    }
+
+    fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        Ok(self
+            .client
+            .get_account_with_commitment(address, CommitmentConfig::confirmed())?
+            .value
+            .map(|account| account.data))
+    }
+}
+
+impl Destination for RpcDestination {
+    fn local_domain(&self) -> Result<u32> {
+        let data = self
+            .account_data(&pda::cpay::message_transmitter())?
+            .context("message transmitter is not initialized")?;
+        let message_transmitter: MessageTransmitter = deserialize(&data).map_err(anchor_error)?;
+        Ok(message_transmitter.local_domain)
+    }
+
+    fn is_received(&self, source_domain: u32, nonce: u64) -> Result<bool> {
+        let Some(data) = self.account_data(&pda::cpay::used_nonces(source_domain, nonce))? else {
+            return Ok(false);
+        };
+        let used_nonces: UsedNonces = deserialize(&data).map_err(anchor_error)?;
+        Ok(used_nonces.is_nonce_used(nonce).unwrap_or(false))
+    }
+
+    fn receive_message(&self, message: &[u8], signatures: Signatures) -> Result<String> {
+        // 按代币对找到本链代币，再派生金库账户
+        let parsed = Message::parse(message).map_err(anchor_error)?;
+        let body = BurnMessageBody::parse(&parsed.message_body).map_err(anchor_error)?;
+        let token_pair_address = pda::cpay::token_pair(parsed.source_domain, &body.burn_token);
+        let data = self.account_data(&token_pair_address)?.ok_or_else(|| {
+            anyhow!(
+                "token pair for domain {} token 0x{} is not linked",
+                parsed.source_domain,
+                hex::encode(body.burn_token)
+            )
+        })?;
+        let token_pair: TokenPair = deserialize(&data).map_err(anchor_error)?;
+
+        let instruction = Treasury::new(token_pair.local_token).receive_message(
+            self.relayer.pubkey(),
+            message.to_vec(),
+            signatures,
+        )
+        .map_err(anchor_error)?;
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.relayer.pubkey()),
+            &[&self.relayer],
+            self.client.get_latest_blockhash()?,
+        );
+        Ok(self
+            .client
+            .send_and_confirm_transaction(&transaction)?
+            .to_string())
+    }
+}
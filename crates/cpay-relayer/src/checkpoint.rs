@@ -0,0 +1,85 @@
+//! 中继进度检查点，保存为JSON文件，重启后从上次处理到的源链交易继续
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// 等待中继的消息
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingMessage {
+    pub source_domain: u32,
+    pub nonce: u64,
+    /// 十六进制编码的消息字节
+    pub message: String,
+    /// 发出消息的源链交易
+    pub source_signature: String,
+    /// 已失败的提交次数
+    pub attempts: u32,
+    /// 下次处理时间（unix 秒）
+    pub next_attempt_at: i64,
+    pub last_error: Option<String>,
+}
+
+impl PendingMessage {
+    pub fn new(source_domain: u32, nonce: u64, message: &[u8], source_signature: String) -> Self {
+        Self {
+            source_domain,
+            nonce,
+            message: hex::encode(message),
+            source_signature,
+            attempts: 0,
+            next_attempt_at: 0,
+            last_error: None,
+        }
+    }
+
+    pub fn message_bytes(&self) -> Result<Vec<u8>> {
+        hex::decode(&self.message).context("invalid message hex in checkpoint")
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// 已读取的最新源链交易，下次只读取比它更新的交易
+    pub last_signature: Option<String>,
+    /// 等待签名或提交的消息
+    pub pending: Vec<PendingMessage>,
+    /// 超过最大提交次数的消息
+    pub failed: Vec<PendingMessage>,
+}
+
+impl Checkpoint {
+    /// 读取检查点，文件不存在时从头开始
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let data = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read checkpoint {}", path.display()))?;
+        serde_json::from_str(&data)
+            .with_context(|| format!("failed to parse checkpoint {}", path.display()))
+    }
+
+    /// 先写临时文件再重命名，避免写到一半时退出导致检查点损坏
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut tmp = PathBuf::from(path);
+        tmp.set_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("failed to write checkpoint {}", tmp.display()))?;
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("failed to write checkpoint {}", path.display()))
+    }
+
+    /// 加入待中继消息，同一 (源链域, 序列号) 的消息被替换（replace_deposit_for_burn）时保留最新的一条
+    pub fn add_pending(&mut self, message: PendingMessage) {
+        match self
+            .pending
+            .iter_mut()
+            .find(|p| p.source_domain == message.source_domain && p.nonce == message.nonce)
+        {
+            Some(existing) => *existing = message,
+            None => self.pending.push(message),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! 中继器配置，从JSON文件读取
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Config {
+    /// 源链RPC地址，从这里读取 cpay 的跨链消息事件
+    pub source_rpc_url: String,
+    /// 目标链RPC地址，在这里提交 receive_message
+    pub destination_rpc_url: String,
+    /// 中继者密钥文件，对应账户必须在目标链 cpay 上拥有中继者角色
+    pub keypair: PathBuf,
+    /// 检查点文件
+    pub checkpoint: PathBuf,
+    /// 验证人签名来源
+    pub attestation: AttestationConfig,
+    /// 轮询间隔（秒）
+    #[serde(default = "default_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    /// 首次重试的等待时间（秒），之后每次失败翻倍
+    #[serde(default = "default_retry_delay_secs")]
+    pub retry_delay_secs: i64,
+    /// 提交失败的最大次数，超过后消息转入失败列表等待人工处理
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttestationConfig {
+    /// Circle 的签名服务，如 https://iris-api.circle.com
+    Http { url: String },
+    /// 本地验证人私钥文件，用于本地验证器测试
+    Local { key_file: PathBuf },
+}
+
+fn default_poll_interval_secs() -> u64 {
+    5
+}
+
+fn default_retry_delay_secs() -> i64 {
+    10
+}
+
+fn default_max_attempts() -> u32 {
+    5
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read config {}", path.display()))?;
+        serde_json::from_str(&data)
+            .with_context(|| format!("failed to parse config {}", path.display()))
+    }
+}
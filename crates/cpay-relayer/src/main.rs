@@ -0,0 +1,84 @@
+//! cpay 跨链消息中继器
+//!
+//! 读取源链 cpay 发出的跨链消息，获取验证人签名后在目标链提交 receive_message。
+//! 用法：`cpay-relayer <config.json>`，配置见 [`config::Config`]
+
+mod attestation;
+mod chain;
+mod checkpoint;
+mod config;
+mod relayer;
+mod tests;
+
+use std::path::Path;
+use std::thread::sleep;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context, Result};
+use log::{error, info};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{read_keypair_file, Signer};
+
+use crate::attestation::{AttestationSource, HttpAttestationSource, LocalAttester};
+use crate::chain::{RpcDestination, RpcSource};
+use crate::config::{AttestationConfig, Config};
+use crate::relayer::Relayer;
+
+fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let path = std::env::args()
+        .nth(1)
+        .context("usage: cpay-relayer <config.json>")?;
+    let config = Config::load(Path::new(&path))?;
+
+    let attestation: Box<dyn AttestationSource> = match &config.attestation {
+        AttestationConfig::Http { url } => Box::new(HttpAttestationSource::new(url)),
+        AttestationConfig::Local { key_file } => {
+            let attester = LocalAttester::from_file(key_file)?;
+            for address in attester.attesters() {
+                info!("using local attester {}", address);
+            }
+            Box::new(attester)
+        }
+    };
+
+    let relayer_keypair = read_keypair_file(&config.keypair)
+        .map_err(|e| anyhow!("failed to read keypair {}: {}", config.keypair.display(), e))?;
+    info!("relaying as {}", relayer_keypair.pubkey());
+
+    let source = RpcSource::new(
+        RpcClient::new_with_commitment(config.source_rpc_url.clone(), CommitmentConfig::confirmed()),
+        cpay::ID,
+    );
+    let destination = RpcDestination::new(
+        RpcClient::new_with_commitment(
+            config.destination_rpc_url.clone(),
+            CommitmentConfig::confirmed(),
+        ),
+        relayer_keypair,
+    );
+    let mut relayer = Relayer::new(
+        source,
+        destination,
+        attestation,
+        config.checkpoint.clone(),
+        config.retry_delay_secs,
+        config.max_attempts,
+    )?;
+
+    loop {
+        if let Err(e) = relayer.run_once(now()) {
+            error!("{:#}", e);
+        }
+        sleep(Duration::from_secs(config.poll_interval_secs));
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
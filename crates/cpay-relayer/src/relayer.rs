@@ -0,0 +1,202 @@
+//! 中继流程：读取源链消息 -> 获取验证人签名 -> 在目标链提交 receive_message
+//!
+//! 提交前后都会检查目标链上的已使用序列号，已被接收（包括被其他中继者接收）的消息直接完成，
+//! 因此重启或重复提交都不会重复铸造
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use cpay::circle::Message;
+use log::{debug, info, warn};
+
+use crate::attestation::AttestationSource;
+use crate::chain::{anchor_error, Destination, Source};
+use crate::checkpoint::{Checkpoint, PendingMessage};
+
+// 重试等待时间翻倍的最大次数
+const MAX_BACKOFF_SHIFT: u32 = 6;
+
+pub struct Relayer<S, D, A> {
+    source: S,
+    destination: D,
+    attestation: A,
+    checkpoint: Checkpoint,
+    checkpoint_path: PathBuf,
+    local_domain: u32,
+    retry_delay_secs: i64,
+    max_attempts: u32,
+}
+
+enum Outcome {
+    // 已接收，移出待处理列表
+    Done,
+    // 稍后重试
+    Retry,
+    // 超过最大提交次数
+    Failed,
+}
+
+impl<S: Source, D: Destination, A: AttestationSource> Relayer<S, D, A> {
+    pub fn new(
+        source: S,
+        destination: D,
+        attestation: A,
+        checkpoint_path: PathBuf,
+        retry_delay_secs: i64,
+        max_attempts: u32,
+    ) -> Result<Self> {
+        let checkpoint = Checkpoint::load(&checkpoint_path)?;
+        let local_domain = destination.local_domain()?;
+        Ok(Self {
+            source,
+            destination,
+            attestation,
+            checkpoint,
+            checkpoint_path,
+            local_domain,
+            retry_delay_secs,
+            max_attempts,
+        })
+    }
+
+    #[cfg(test)]
+    pub fn checkpoint(&self) -> &Checkpoint {
+        &self.checkpoint
+    }
+
+    /// 执行一轮中继，now 为当前 unix 时间（秒）
+    pub fn run_once(&mut self, now: i64) -> Result<()> {
+        self.poll_source()?;
+        self.process_pending(now)
+    }
+
+    /// 读取源链的新消息，只保留发往目标链的消息
+    pub fn poll_source(&mut self) -> Result<()> {
+        let (messages, latest) = self
+            .source
+            .messages_since(self.checkpoint.last_signature.as_deref())?;
+
+        for sent in messages {
+            let message = match Message::parse(&sent.message).map_err(anchor_error) {
+                Ok(message) => message,
+                Err(e) => {
+                    warn!("skipping malformed message in {}: {}", sent.signature, e);
+                    continue;
+                }
+            };
+            if message.destination_domain != self.local_domain {
+                debug!(
+                    "skipping message {}:{} for domain {}",
+                    message.source_domain, message.nonce, message.destination_domain
+                );
+                continue;
+            }
+            info!(
+                "found message {}:{} in {}",
+                message.source_domain, message.nonce, sent.signature
+            );
+            self.checkpoint.add_pending(PendingMessage::new(
+                message.source_domain,
+                message.nonce,
+                &sent.message,
+                sent.signature,
+            ));
+        }
+
+        self.checkpoint.last_signature = latest;
+        self.checkpoint.save(&self.checkpoint_path)
+    }
+
+    /// 处理到期的待中继消息
+    pub fn process_pending(&mut self, now: i64) -> Result<()> {
+        let mut pending = vec![];
+        for mut message in std::mem::take(&mut self.checkpoint.pending) {
+            if message.next_attempt_at > now {
+                pending.push(message);
+                continue;
+            }
+            match self.process(&mut message, now) {
+                Outcome::Done => {}
+                Outcome::Retry => pending.push(message),
+                Outcome::Failed => {
+                    warn!(
+                        "giving up on message {}:{} after {} attempts",
+                        message.source_domain, message.nonce, message.attempts
+                    );
+                    self.checkpoint.failed.push(message);
+                }
+            }
+        }
+        self.checkpoint.pending = pending;
+        self.checkpoint.save(&self.checkpoint_path)
+    }
+
+    fn process(&self, message: &mut PendingMessage, now: i64) -> Outcome {
+        let (source_domain, nonce) = (message.source_domain, message.nonce);
+        let bytes = match message.message_bytes() {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                message.last_error = Some(e.to_string());
+                return Outcome::Failed;
+            }
+        };
+
+        match self.destination.is_received(source_domain, nonce) {
+            Ok(true) => {
+                info!("message {}:{} already received", source_domain, nonce);
+                return Outcome::Done;
+            }
+            Ok(false) => {}
+            Err(e) => return self.retry_later(message, now, e, false),
+        }
+
+        let signatures = match self.attestation.attestation(&bytes) {
+            Ok(Some(signatures)) => signatures,
+            Ok(None) => {
+                debug!("message {}:{} is waiting for attestation", source_domain, nonce);
+                message.next_attempt_at = now + self.retry_delay_secs;
+                return Outcome::Retry;
+            }
+            Err(e) => return self.retry_later(message, now, e, false),
+        };
+
+        match self.destination.receive_message(&bytes, signatures) {
+            Ok(signature) => {
+                info!("received message {}:{} in {}", source_domain, nonce, signature);
+                Outcome::Done
+            }
+            // 提交失败可能是因为消息已被接收（如确认超时但交易已上链），再检查一次
+            Err(e) => match self.destination.is_received(source_domain, nonce) {
+                Ok(true) => {
+                    info!("message {}:{} already received", source_domain, nonce);
+                    Outcome::Done
+                }
+                _ => self.retry_later(message, now, e, true),
+            },
+        }
+    }
+
+    // 安排重试，只有提交失败计入失败次数，签名服务或RPC的临时错误不计入
+    fn retry_later(
+        &self,
+        message: &mut PendingMessage,
+        now: i64,
+        error: anyhow::Error,
+        count_attempt: bool,
+    ) -> Outcome {
+        warn!(
+            "message {}:{} failed: {:#}",
+            message.source_domain, message.nonce, error
+        );
+        message.last_error = Some(format!("{:#}", error));
+        if count_attempt {
+            message.attempts += 1;
+            if message.attempts >= self.max_attempts {
+                return Outcome::Failed;
+            }
+        }
+        let backoff = message.attempts.saturating_sub(1).min(MAX_BACKOFF_SHIFT);
+        message.next_attempt_at = now + (self.retry_delay_secs << backoff);
+        Outcome::Retry
+    }
+}
@@ -0,0 +1,372 @@
+#[cfg(test)]
+mod attestation_tests {
+    use crate::attestation::{parse_attestation, AttestationSource, LocalAttester};
+    use cpay::circle::{MessageTransmitter, SIGNATURE_LENGTH};
+    use libsecp256k1::SecretKey;
+
+    fn local_attester(seeds: &[u8]) -> LocalAttester {
+        LocalAttester::new(
+            seeds
+                .iter()
+                .map(|seed| SecretKey::parse(&[*seed; 32]).unwrap())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_local_attester_signatures_verify_on_chain() {
+        let attester = local_attester(&[3, 1, 2]);
+        let mut message_transmitter = MessageTransmitter::default();
+        for address in attester.attesters() {
+            message_transmitter.enable_attester(address).unwrap();
+        }
+        message_transmitter.set_signature_threshold(3).unwrap();
+
+        let message = b"cpay message";
+        let signatures = attester.attestation(message).unwrap().unwrap();
+        assert_eq!(signatures.len(), 3);
+        message_transmitter
+            .verify_attestation_signatures(message, &signatures)
+            .unwrap();
+
+        // 签名绑定消息内容
+        assert!(message_transmitter
+            .verify_attestation_signatures(b"other message", &signatures)
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_attestation() {
+        let signatures = parse_attestation(&format!(
+            "0x{}{}",
+            hex::encode([1u8; SIGNATURE_LENGTH]),
+            hex::encode([2u8; SIGNATURE_LENGTH])
+        ))
+        .unwrap();
+        assert_eq!(signatures, vec![[1; SIGNATURE_LENGTH], [2; SIGNATURE_LENGTH]]);
+
+        assert!(parse_attestation("0x").is_err());
+        assert!(parse_attestation(&hex::encode([1u8; SIGNATURE_LENGTH - 1])).is_err());
+        assert!(parse_attestation("not hex").is_err());
+    }
+}
+
+#[cfg(test)]
+mod log_tests {
+    use crate::chain::parse_message_sent;
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::{AnchorSerialize, Discriminator};
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use cpay::circle::MessageSent;
+
+    fn event_log(message: &[u8]) -> String {
+        let mut data = MessageSent::DISCRIMINATOR.to_vec();
+        MessageSent {
+            message: message.to_vec(),
+        }
+        .serialize(&mut data)
+        .unwrap();
+        format!("Program data: {}", STANDARD.encode(data))
+    }
+
+    #[test]
+    fn test_parse_message_sent() {
+        let program_id = Pubkey::new_unique();
+        let other_program = Pubkey::new_unique();
+        let logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            "Program log: Instruction: DepositForBurn".to_string(),
+            format!("Program {} invoke [2]", other_program),
+            // 被调用程序发出的同名事件不计入
+            event_log(b"from other program"),
+            format!("Program {} success", other_program),
+            "Program data: not base64!".to_string(),
+            event_log(b"message"),
+            format!("Program {} consumed 50000 of 200000 compute units", program_id),
+            format!("Program {} success", program_id),
+            // 其他顶层指令
+            format!("Program {} invoke [1]", other_program),
+            event_log(b"from other instruction"),
+            format!("Program {} failed: custom program error: 0x1", other_program),
+        ];
+
+        assert_eq!(
+            parse_message_sent(&program_id, &logs),
+            vec![b"message".to_vec()]
+        );
+    }
+}
+
+#[cfg(test)]
+mod checkpoint_tests {
+    use crate::checkpoint::{Checkpoint, PendingMessage};
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!(
+            "cpay-relayer-checkpoint-{}.json",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(Checkpoint::load(&path).unwrap(), Checkpoint::default());
+
+        let mut checkpoint = Checkpoint {
+            last_signature: Some("sig".to_string()),
+            ..Checkpoint::default()
+        };
+        checkpoint.add_pending(PendingMessage::new(0, 1, &[1, 2, 3], "a".to_string()));
+        checkpoint.save(&path).unwrap();
+        assert_eq!(Checkpoint::load(&path).unwrap(), checkpoint);
+        assert_eq!(checkpoint.pending[0].message_bytes().unwrap(), vec![1, 2, 3]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_replaced_message_overrides_pending() {
+        let mut checkpoint = Checkpoint::default();
+        checkpoint.add_pending(PendingMessage::new(0, 1, &[1], "a".to_string()));
+        checkpoint.add_pending(PendingMessage::new(0, 2, &[2], "b".to_string()));
+        checkpoint.add_pending(PendingMessage::new(1, 1, &[3], "c".to_string()));
+        checkpoint.add_pending(PendingMessage::new(0, 1, &[4], "d".to_string()));
+
+        assert_eq!(checkpoint.pending.len(), 3);
+        assert_eq!(checkpoint.pending[0].message_bytes().unwrap(), vec![4]);
+        assert_eq!(checkpoint.pending[0].source_signature, "d");
+    }
+}
+
+#[cfg(test)]
+mod relayer_tests {
+    use std::cell::{Cell, RefCell};
+    use std::collections::BTreeSet;
+    use std::path::PathBuf;
+
+    use anyhow::{bail, Result};
+    use cpay::circle::{BurnMessageBody, Message};
+
+    use crate::attestation::{AttestationSource, Signatures};
+    use crate::chain::{Destination, SentMessage, Source};
+    use crate::relayer::Relayer;
+
+    const LOCAL_DOMAIN: u32 = 5;
+
+    #[derive(Default)]
+    struct MockSource {
+        transactions: RefCell<Vec<SentMessage>>,
+    }
+
+    impl MockSource {
+        fn send(&self, signature: &str, message: Vec<u8>) {
+            self.transactions.borrow_mut().push(SentMessage {
+                signature: signature.to_string(),
+                message,
+            });
+        }
+    }
+
+    impl Source for &MockSource {
+        fn messages_since(&self, until: Option<&str>) -> Result<(Vec<SentMessage>, Option<String>)> {
+            let transactions = self.transactions.borrow();
+            let start = until
+                .and_then(|until| transactions.iter().position(|t| t.signature == until))
+                .map_or(0, |index| index + 1);
+            Ok((
+                transactions[start..].to_vec(),
+                transactions.last().map(|t| t.signature.clone()),
+            ))
+        }
+    }
+
+    #[derive(Default)]
+    struct MockDestination {
+        received: RefCell<BTreeSet<(u32, u64)>>,
+        submissions: Cell<u32>,
+        // 前 n 次提交失败
+        failures: Cell<u32>,
+        // 提交失败但交易实际已上链
+        lands_on_failure: Cell<bool>,
+    }
+
+    impl Destination for &MockDestination {
+        fn local_domain(&self) -> Result<u32> {
+            Ok(LOCAL_DOMAIN)
+        }
+
+        fn is_received(&self, source_domain: u32, nonce: u64) -> Result<bool> {
+            Ok(self.received.borrow().contains(&(source_domain, nonce)))
+        }
+
+        fn receive_message(&self, message: &[u8], signatures: Signatures) -> Result<String> {
+            assert_eq!(signatures.len(), 1);
+            self.submissions.set(self.submissions.get() + 1);
+            let message = Message::parse(message).unwrap();
+            let key = (message.source_domain, message.nonce);
+            if self.failures.get() > 0 {
+                self.failures.set(self.failures.get() - 1);
+                if self.lands_on_failure.get() {
+                    self.received.borrow_mut().insert(key);
+                }
+                bail!("transaction failed");
+            }
+            if !self.received.borrow_mut().insert(key) {
+                bail!("nonce already used");
+            }
+            Ok(format!("receive-{}", message.nonce))
+        }
+    }
+
+    struct MockAttestation {
+        ready: Cell<bool>,
+    }
+
+    impl AttestationSource for &MockAttestation {
+        fn attestation(&self, _message: &[u8]) -> Result<Option<Signatures>> {
+            Ok(self.ready.get().then(|| vec![[0; 65]]))
+        }
+    }
+
+    fn message(destination_domain: u32, nonce: u64) -> Vec<u8> {
+        Message {
+            version: 0,
+            source_domain: 0,
+            destination_domain,
+            nonce,
+            sender: [4; 32],
+            recipient: [5; 32],
+            destination_caller: [0; 32],
+            message_body: BurnMessageBody {
+                version: 0,
+                burn_token: [1; 32],
+                mint_recipient: [2; 32],
+                amount: 1_000_000,
+                message_sender: [3; 32],
+            }
+            .serialize(),
+        }
+        .serialize()
+    }
+
+    fn checkpoint_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "cpay-relayer-{}-{}.json",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_relays_each_message_once() {
+        let source = MockSource::default();
+        let destination = MockDestination::default();
+        let attestation = MockAttestation {
+            ready: Cell::new(false),
+        };
+        let path = checkpoint_path("once");
+
+        source.send("tx1", message(LOCAL_DOMAIN, 1));
+        // 发往其他链的消息不处理
+        source.send("tx2", message(LOCAL_DOMAIN + 1, 2));
+
+        let mut relayer =
+            Relayer::new(&source, &destination, &attestation, path.clone(), 10, 3).unwrap();
+
+        // 等待签名
+        relayer.run_once(100).unwrap();
+        assert_eq!(relayer.checkpoint().pending.len(), 1);
+        assert_eq!(relayer.checkpoint().pending[0].next_attempt_at, 110);
+        assert_eq!(relayer.checkpoint().last_signature.as_deref(), Some("tx2"));
+        assert_eq!(destination.submissions.get(), 0);
+
+        // 签名就绪，但未到重试时间
+        attestation.ready.set(true);
+        relayer.run_once(105).unwrap();
+        assert_eq!(destination.submissions.get(), 0);
+
+        relayer.run_once(110).unwrap();
+        assert_eq!(destination.submissions.get(), 1);
+        assert!(relayer.checkpoint().pending.is_empty());
+        assert!(destination.received.borrow().contains(&(0, 1)));
+
+        // 重启后从检查点继续，不会重复读取旧消息
+        source.send("tx3", message(LOCAL_DOMAIN, 3));
+        let mut relayer =
+            Relayer::new(&source, &destination, &attestation, path.clone(), 10, 3).unwrap();
+        relayer.run_once(120).unwrap();
+        assert_eq!(destination.submissions.get(), 2);
+        assert_eq!(relayer.checkpoint().last_signature.as_deref(), Some("tx3"));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_skips_messages_already_received() {
+        let source = MockSource::default();
+        let destination = MockDestination::default();
+        let attestation = MockAttestation {
+            ready: Cell::new(true),
+        };
+        let path = checkpoint_path("received");
+
+        // 其他中继者已经提交
+        destination.received.borrow_mut().insert((0, 1));
+        source.send("tx1", message(LOCAL_DOMAIN, 1));
+
+        let mut relayer =
+            Relayer::new(&source, &destination, &attestation, path.clone(), 10, 3).unwrap();
+        relayer.run_once(100).unwrap();
+        assert_eq!(destination.submissions.get(), 0);
+        assert!(relayer.checkpoint().pending.is_empty());
+
+        // 提交失败但交易已上链
+        destination.failures.set(1);
+        destination.lands_on_failure.set(true);
+        source.send("tx2", message(LOCAL_DOMAIN, 2));
+        relayer.run_once(100).unwrap();
+        assert_eq!(destination.submissions.get(), 1);
+        assert!(relayer.checkpoint().pending.is_empty());
+        assert!(relayer.checkpoint().failed.is_empty());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_retries_with_backoff_then_fails() {
+        let source = MockSource::default();
+        let destination = MockDestination::default();
+        let attestation = MockAttestation {
+            ready: Cell::new(true),
+        };
+        let path = checkpoint_path("retry");
+
+        destination.failures.set(u32::MAX);
+        source.send("tx1", message(LOCAL_DOMAIN, 1));
+
+        let mut relayer =
+            Relayer::new(&source, &destination, &attestation, path.clone(), 10, 3).unwrap();
+        relayer.run_once(100).unwrap();
+        let pending = &relayer.checkpoint().pending[0];
+        assert_eq!(pending.attempts, 1);
+        assert_eq!(pending.next_attempt_at, 110);
+        assert_eq!(pending.last_error.as_deref(), Some("transaction failed"));
+
+        relayer.run_once(110).unwrap();
+        assert_eq!(relayer.checkpoint().pending[0].attempts, 2);
+        assert_eq!(relayer.checkpoint().pending[0].next_attempt_at, 130);
+
+        relayer.run_once(130).unwrap();
+        assert_eq!(destination.submissions.get(), 3);
+        assert!(relayer.checkpoint().pending.is_empty());
+        assert_eq!(relayer.checkpoint().failed.len(), 1);
+        assert_eq!(relayer.checkpoint().failed[0].attempts, 3);
+
+        // 失败的消息不再提交
+        relayer.run_once(1_000).unwrap();
+        assert_eq!(destination.submissions.get(), 3);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
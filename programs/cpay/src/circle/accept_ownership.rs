@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use super::state::*;
+
+// 接受消息传输器管理员转移的指令账户结构
+#[derive(Accounts)]
+pub struct AcceptOwnership<'info> {
+    // 待接受的新管理员
+    pub pending_authority: Signer<'info>,
+
+    // 消息传输器账户，通过PDA派生
+    #[account(
+        mut,
+        seeds = [b"message_transmitter"],
+        bump,
+        has_one = pending_authority @ ErrorCode::InvalidPendingAuthority,
+    )]
+    pub message_transmitter: Account<'info, MessageTransmitter>,
+}
+
+// 接受代币消息传输器管理员转移的指令账户结构
+#[derive(Accounts)]
+pub struct AcceptTokenMessengerOwnership<'info> {
+    // 待接受的新管理员
+    pub pending_authority: Signer<'info>,
+
+    // 代币消息传输器账户
+    #[account(
+        mut,
+        has_one = pending_authority @ ErrorCode::InvalidPendingAuthority,
+    )]
+    pub token_messenger: Account<'info, TokenMessenger>,
+}
+
+// 接受消息传输器管理员转移
+pub fn accept_ownership(ctx: Context<AcceptOwnership>) -> Result<()> {
+    let message_transmitter = &mut ctx.accounts.message_transmitter;
+    let previous_authority = message_transmitter.authority;
+    message_transmitter.authority = message_transmitter.pending_authority;
+    message_transmitter.pending_authority = Pubkey::default();
+
+    emit!(OwnershipTransferred {
+        account: message_transmitter.key(),
+        previous_authority,
+        new_authority: message_transmitter.authority,
+    });
+
+    Ok(())
+}
+
+// 接受代币消息传输器管理员转移
+pub fn accept_token_messenger_ownership(ctx: Context<AcceptTokenMessengerOwnership>) -> Result<()> {
+    let token_messenger = &mut ctx.accounts.token_messenger;
+    let previous_authority = token_messenger.authority;
+    token_messenger.authority = token_messenger.pending_authority;
+    token_messenger.pending_authority = Pubkey::default();
+
+    emit!(OwnershipTransferred {
+        account: token_messenger.key(),
+        previous_authority,
+        new_authority: token_messenger.authority,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use super::state::*;
+
+// 添加远端代币消息传输器的指令账户结构
+#[derive(Accounts)]
+#[instruction(domain: u32)]
+pub struct AddRemoteTokenMessenger<'info> {
+    // 管理员，必须是消息传输器的 authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // 消息传输器账户，通过PDA派生
+    #[account(
+        seeds = [b"message_transmitter"],
+        bump,
+        has_one = authority @ ErrorCode::UnauthorizedAdmin,
+    )]
+    pub message_transmitter: Account<'info, MessageTransmitter>,
+
+    // 远端代币消息传输器账户，按目标链域ID派生
+    #[account(
+        init,
+        payer = authority,
+        space = RemoteTokenMessenger::LEN,
+        seeds = [b"remote_token_messenger", domain.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub remote_token_messenger: Account<'info, RemoteTokenMessenger>,
+
+    // 系统程序
+    pub system_program: Program<'info, System>,
+}
+
+// 添加远端代币消息传输器的处理函数
+pub fn add_remote_token_messenger(
+    ctx: Context<AddRemoteTokenMessenger>,
+    domain: u32,                    // 目标链域ID
+    token_messenger: [u8; 32],      // 目标链上的 TokenMessenger 地址
+) -> Result<()> {
+    require!(
+        domain != ctx.accounts.message_transmitter.local_domain,
+        ErrorCode::InvalidDestinationDomain
+    );
+    require!(token_messenger != [0u8; 32], ErrorCode::InvalidRemoteTokenMessenger);
+
+    let remote_token_messenger = &mut ctx.accounts.remote_token_messenger;
+    remote_token_messenger.domain = domain;
+    remote_token_messenger.token_messenger = token_messenger;
+
+    emit!(RemoteTokenMessengerAdded {
+        domain,
+        token_messenger,
+    });
+
+    Ok(())
+}
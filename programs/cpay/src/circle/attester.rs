@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use anchor_lang::solana_program::secp256k1_recover::secp256k1_recover;
+use crate::error::ErrorCode;
+use super::state::*;
+
+// 单个签名长度（r || s || v）
+pub const SIGNATURE_LENGTH: usize = 65;
+
+// secp256k1 曲线阶的一半，s 值大于它的签名视为可延展签名并拒绝
+const HALF_CURVE_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d,
+    0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+impl MessageTransmitter {
+    // 判断验证人是否已启用
+    pub fn is_enabled_attester(&self, attester: &Pubkey) -> bool {
+        self.enabled_attesters.contains(attester)
+    }
+
+    // 启用验证人
+    pub fn enable_attester(&mut self, attester: Pubkey) -> Result<()> {
+        require!(attester != Pubkey::default(), ErrorCode::InvalidAttester);
+        require!(
+            !self.is_enabled_attester(&attester),
+            ErrorCode::AttesterAlreadyEnabled
+        );
+        require!(
+            self.enabled_attesters.len() < Self::MAX_ATTESTERS,
+            ErrorCode::TooManyAttesters
+        );
+
+        self.enabled_attesters.push(attester);
+        Ok(())
+    }
+
+    // 禁用验证人，禁用后剩余验证人数量必须仍然满足签名阈值
+    pub fn disable_attester(&mut self, attester: Pubkey) -> Result<()> {
+        let index = self
+            .enabled_attesters
+            .iter()
+            .position(|enabled| enabled == &attester)
+            .ok_or(ErrorCode::AttesterNotEnabled)?;
+        require!(
+            self.enabled_attesters.len() > 1
+                && self.enabled_attesters.len() > self.signature_threshold as usize,
+            ErrorCode::TooFewEnabledAttesters
+        );
+
+        self.enabled_attesters.remove(index);
+        Ok(())
+    }
+
+    // 设置签名阈值，阈值必须大于0且不超过已启用的验证人数量
+    pub fn set_signature_threshold(&mut self, new_signature_threshold: u32) -> Result<()> {
+        require!(
+            new_signature_threshold > 0
+                && new_signature_threshold as usize <= self.enabled_attesters.len(),
+            ErrorCode::InvalidSignatureThreshold
+        );
+
+        self.signature_threshold = new_signature_threshold;
+        Ok(())
+    }
+
+    // 验证消息的验证人签名
+    // 签名数量必须等于阈值，恢复出的验证人地址必须严格递增（排序且不重复）并且已启用
+    pub fn verify_attestation_signatures(
+        &self,
+        message: &[u8],
+        signatures: &[[u8; SIGNATURE_LENGTH]],
+    ) -> Result<()> {
+        require!(
+            self.signature_threshold > 0
+                && signatures.len() == self.signature_threshold as usize,
+            ErrorCode::InvalidAttestationLength
+        );
+
+        let digest = keccak::hash(message).to_bytes();
+
+        // 上一个恢复出的验证人地址，用于检查排序和重复
+        let mut latest_attester = Pubkey::default();
+        for signature in signatures {
+            let attester = recover_attester(&digest, signature)?;
+            require!(
+                attester > latest_attester,
+                ErrorCode::InvalidSignatureOrderOrDupe
+            );
+            require!(
+                self.is_enabled_attester(&attester),
+                ErrorCode::InvalidAttester
+            );
+            latest_attester = attester;
+        }
+
+        Ok(())
+    }
+}
+
+// 从签名中恢复验证人的以太坊地址，返回左侧补零到32字节的 Pubkey
+pub fn recover_attester(digest: &[u8; 32], signature: &[u8; SIGNATURE_LENGTH]) -> Result<Pubkey> {
+    // v 只接受以太坊格式的 27/28
+    let v = signature[64];
+    require!(v == 27 || v == 28, ErrorCode::InvalidSignature);
+    let recovery_id = v - 27;
+
+    // 拒绝高 s 值，避免同一消息存在两个有效签名
+    require!(
+        signature[32..64] <= HALF_CURVE_ORDER[..],
+        ErrorCode::InvalidSignature
+    );
+
+    let pubkey = secp256k1_recover(digest, recovery_id, &signature[..64])
+        .map_err(|_| error!(ErrorCode::InvalidSignature))?;
+
+    // 以太坊地址为公钥 keccak 哈希的后20字节
+    let mut address = keccak::hash(&pubkey.to_bytes()).to_bytes();
+    address[..12].fill(0);
+    Ok(Pubkey::new_from_array(address))
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use super::state::*;
+
+// 默认时间窗口时长（24小时）
+pub const DEFAULT_BURN_WINDOW_DURATION: i64 = 24 * 60 * 60;
+
+impl TokenMessenger {
+    // 当前窗口是否已结束，窗口从 burn_window_start 开始持续 burn_window_duration 秒
+    fn burn_window_expired(&self, now: i64) -> bool {
+        self.burn_window_duration <= 0
+            || now >= self.burn_window_start.saturating_add(self.burn_window_duration)
+    }
+
+    // 计算当前时间窗口内已计入限额的销毁数量，窗口结束后归零
+    pub fn burned_in_window_at(&self, now: i64) -> u64 {
+        if self.burn_window_expired(now) {
+            0
+        } else {
+            self.burned_in_window
+        }
+    }
+
+    // 检查销毁限额并记录本次销毁
+    pub fn record_burn(&mut self, amount: u64, now: i64) -> Result<()> {
+        require!(
+            self.max_burn_amount_per_message == 0 || amount <= self.max_burn_amount_per_message,
+            ErrorCode::BurnAmountExceedsMessageLimit
+        );
+
+        if self.burn_limit_per_window > 0 {
+            // 窗口结束后以本次销毁时间开启新窗口
+            if self.burn_window_expired(now) {
+                self.burned_in_window = 0;
+                self.burn_window_start = now;
+            }
+
+            let burned = self
+                .burned_in_window
+                .checked_add(amount)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(
+                burned <= self.burn_limit_per_window,
+                ErrorCode::BurnAmountExceedsWindowLimit
+            );
+
+            self.burned_in_window = burned;
+        }
+
+        Ok(())
+    }
+
+    // 更新销毁限额
+    pub fn set_burn_limits(
+        &mut self,
+        max_burn_amount_per_message: u64,
+        burn_limit_per_window: u64,
+        burn_window_duration: i64,
+        now: i64,
+    ) -> Result<()> {
+        require!(
+            burn_limit_per_window == 0 || burn_window_duration > 0,
+            ErrorCode::InvalidArgument
+        );
+
+        // 旧窗口已结束时从当前时间开启新窗口，未结束时沿用原窗口
+        if self.burn_window_expired(now) {
+            self.burned_in_window = 0;
+            self.burn_window_start = now;
+        }
+
+        self.max_burn_amount_per_message = max_burn_amount_per_message;
+        self.burn_limit_per_window = burn_limit_per_window;
+        self.burn_window_duration = burn_window_duration;
+        Ok(())
+    }
+}
@@ -1,21 +1,42 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Burn, Token, TokenAccount, Mint};
 use crate::error::ErrorCode;
+use crate::instructions::GlobalState;
 use crate::state::*;
+use super::message::*;
 use super::state::*;
 
 // 销毁代币并创建跨链转账消息的指令账户结构
 #[derive(Accounts)]
+#[instruction(amount: u64, destination_domain: u32)]
 pub struct DepositForBurn<'info> {
-    // 交易发起人，必须签名
+    // 交易发起人，必须是操作员
     #[account(mut)]
     pub authority: Signer<'info>,
+
+    // 全局状态账户，程序金库代币账户的authority，同时汇总所有金库的资金
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    // 权限控制账户，调用者必须拥有操作员角色
+    #[account(
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        constraint = access_control.has_role(Role::Operator, &authority.key()) @ ErrorCode::UnauthorizedRole,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
     
     // 程序的USDC代币账户，通过PDA派生
     #[account(
         mut,
         seeds = [b"program_token_account", mint.key().as_ref()],
         bump,
+        token::mint = mint,
+        token::authority = global_state,
     )]
     pub program_token_account: Account<'info, TokenAccount>,
 
@@ -31,6 +52,14 @@ pub struct DepositForBurn<'info> {
     #[account(mut)]
     pub mint: Account<'info, Mint>,
     
+    // 消息传输器账户，提供本链域ID、消息版本和本链域全局的消息序列号
+    #[account(
+        mut,
+        seeds = [b"message_transmitter"],
+        bump,
+    )]
+    pub message_transmitter: Account<'info, MessageTransmitter>,
+    
     // 代币消息传输器账户，通过PDA派生
     #[account(
         mut,
@@ -39,12 +68,20 @@ pub struct DepositForBurn<'info> {
     )]
     pub token_messenger: Account<'info, TokenMessenger>,
     
+    // 目标链的远端代币消息传输器账户，未注册的目标链域会被拒绝
+    #[account(
+        seeds = [b"remote_token_messenger", destination_domain.to_le_bytes().as_ref()],
+        bump,
+        constraint = remote_token_messenger.domain == destination_domain @ ErrorCode::InvalidDestinationDomain,
+    )]
+    pub remote_token_messenger: Account<'info, RemoteTokenMessenger>,
+    
     // 销毁消息账户，用于记录本次跨链转账信息
     #[account(
         init,
         payer = authority,
         space = 8 + std::mem::size_of::<BurnMessage>(),
-        seeds = [b"burn_message", token_messenger.nonce.to_le_bytes().as_ref()],
+        seeds = [b"burn_message", message_transmitter.nonce.to_le_bytes().as_ref()],
         bump
     )]
     pub burn_message: Account<'info, BurnMessage>,
@@ -62,45 +99,109 @@ pub fn deposit_for_burn(
     destination_domain: u32,        // 目标链域ID
     mint_recipient: [u8; 32],       // 目标链接收方地址
 ) -> Result<()> {
+    deposit_for_burn_internal(ctx, amount, destination_domain, mint_recipient, [0u8; 32])
+}
+
+// 销毁代币并创建只能由指定调用者在目标链接收的跨链转账消息
+pub fn deposit_for_burn_with_caller(
+    ctx: Context<DepositForBurn>,
+    amount: u64,                    // 要销毁的代币数量
+    destination_domain: u32,        // 目标链域ID
+    mint_recipient: [u8; 32],       // 目标链接收方地址
+    destination_caller: [u8; 32],   // 目标链上允许接收消息的调用者
+) -> Result<()> {
+    require!(destination_caller != [0u8; 32], ErrorCode::InvalidDestinationCaller);
+
+    deposit_for_burn_internal(ctx, amount, destination_domain, mint_recipient, destination_caller)
+}
+
+// 根据销毁消息记录构建 CCTP v1 格式的跨链消息
+pub(crate) fn build_message(
+    message_transmitter: &MessageTransmitter,
+    token_messenger: &Account<TokenMessenger>,
+    remote_token_messenger: &RemoteTokenMessenger,
+    burn_message: &BurnMessage,
+) -> Message {
+    Message {
+        version: message_transmitter.version,
+        source_domain: message_transmitter.local_domain,
+        destination_domain: burn_message.remote_domain,
+        nonce: burn_message.nonce,
+        sender: token_messenger.key().to_bytes(),
+        recipient: remote_token_messenger.token_messenger,
+        destination_caller: burn_message.destination_caller,
+        message_body: BurnMessageBody {
+            version: token_messenger.message_body_version,
+            burn_token: burn_message.mint.to_bytes(),
+            mint_recipient: burn_message.recipient,
+            amount: burn_message.amount,
+            message_sender: burn_message.authority.to_bytes(),
+        }
+        .serialize(),
+    }
+}
+
+fn deposit_for_burn_internal(
+    ctx: Context<DepositForBurn>,
+    amount: u64,
+    destination_domain: u32,
+    mint_recipient: [u8; 32],
+    destination_caller: [u8; 32],
+) -> Result<()> {
+    // 验证程序级跨链转出操作未暂停
+    ctx.accounts.global_state.require_not_paused(GlobalState::PAUSE_BURN)?;
+
+    let message_transmitter = &mut ctx.accounts.message_transmitter;
     let token_messenger = &mut ctx.accounts.token_messenger;
     let burn_message = &mut ctx.accounts.burn_message;
     let fund_account = &mut ctx.accounts.fund_account;
     
+    // 验证接收方地址有效
+    require!(mint_recipient != [0u8; 32], ErrorCode::InvalidMintRecipient);
+
     // 验证闲置资金是否足够
     require!(
         fund_account.idle_amount >= amount,
         ErrorCode::InsufficientIdleFunds
     );
     
+    // 检查单条消息和时间窗口销毁限额
+    token_messenger.record_burn(amount, Clock::get()?.unix_timestamp)?;
+    
     // 创建销毁消息记录
     burn_message.amount = amount;
     burn_message.mint = ctx.accounts.mint.key();
     burn_message.remote_domain = destination_domain;
     burn_message.recipient = mint_recipient;
-    burn_message.nonce = token_messenger.nonce;
+    burn_message.nonce = message_transmitter.nonce;
+    burn_message.authority = ctx.accounts.authority.key();
+    burn_message.destination_caller = destination_caller;
+    burn_message.version = 0;
+    burn_message.payer = ctx.accounts.authority.key();
+    burn_message.created_at = Clock::get()?.unix_timestamp;
     
-    // 增加消息序列号
-    token_messenger.nonce = token_messenger.nonce.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
-    
-    // 获取mint的公钥
-    let mint_key = ctx.accounts.mint.key();
+    // 构建 CCTP v1 格式的跨链消息
+    let message = build_message(
+        message_transmitter,
+        token_messenger,
+        &ctx.accounts.remote_token_messenger,
+        burn_message,
+    );
     
-    // 准备程序PDA的签名种子
-    let program_token_seeds: &[&[&[u8]]] = &[&[
-        b"program_token_account",
-        mint_key.as_ref(),
-        &[ctx.bumps.program_token_account],
-    ]];
+    // 增加消息序列号，序列号由本链域所有代币共用，不同代币的消息不会重复
+    message_transmitter.nonce = message_transmitter.nonce.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
     
-    // 构建销毁代币的CPI上下文，使用程序PDA签名
+    // 构建销毁代币的CPI上下文，使用全局状态PDA签名
+    let global_state_seeds = ctx.accounts.global_state.signer_seeds();
+    let signer_seeds: &[&[&[u8]]] = &[&global_state_seeds];
     let burn_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
         Burn {
             mint: ctx.accounts.mint.to_account_info(),
             from: ctx.accounts.program_token_account.to_account_info(),
-            authority: ctx.accounts.program_token_account.to_account_info(), // 程序PDA作为authority
+            authority: ctx.accounts.global_state.to_account_info(), // 全局状态PDA作为authority
         },
-        program_token_seeds,
+        signer_seeds,
     );
     
     // 执行代币销毁
@@ -112,6 +213,7 @@ pub fn deposit_for_burn(
     fund_account.sent_amount = fund_account.sent_amount.checked_add(amount)
         .ok_or(ErrorCode::MathOverflow)?;
     fund_account.last_update_time = Clock::get()?.unix_timestamp;
+    ctx.accounts.global_state.record_sent(amount)?;
 
     // 发出跨链资金发送事件
     emit!(CrossChainFundSent {
@@ -121,6 +223,11 @@ pub fn deposit_for_burn(
         timestamp: fund_account.last_update_time,
     });
 
+    // 发出跨链消息事件，供中继器获取验证人签名
+    emit!(MessageSent {
+        message: message.serialize(),
+    });
+
     // 发出资金状态变更事件
     emit!(FundStatusChanged {
         amount,
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use super::state::*;
+
+// 启用验证人的指令账户结构
+#[derive(Accounts)]
+pub struct EnableAttester<'info> {
+    // 管理员，必须是消息传输器的 authority
+    pub authority: Signer<'info>,
+
+    // 消息传输器账户，通过PDA派生
+    #[account(
+        mut,
+        seeds = [b"message_transmitter"],
+        bump,
+        has_one = authority @ ErrorCode::UnauthorizedAdmin,
+    )]
+    pub message_transmitter: Account<'info, MessageTransmitter>,
+}
+
+// 启用验证人的处理函数
+pub fn enable_attester(
+    ctx: Context<EnableAttester>,
+    new_attester: Pubkey,           // 新验证人地址
+) -> Result<()> {
+    ctx.accounts.message_transmitter.enable_attester(new_attester)?;
+
+    emit!(AttesterEnabled {
+        attester: new_attester,
+    });
+
+    Ok(())
+}
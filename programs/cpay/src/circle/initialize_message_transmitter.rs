@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::AccessControl;
+use super::state::*;
+
+// 初始化消息传输器的参数
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct InitializeMessageTransmitterParams {
+    // 本链域ID
+    pub local_domain: u32,
+    // 消息格式版本
+    pub version: u32,
+    // 初始验证人地址（以太坊地址，左侧补零到32字节）
+    pub attester: Pubkey,
+}
+
+// 初始化消息传输器的指令账户结构
+#[derive(Accounts)]
+pub struct InitializeMessageTransmitter<'info> {
+    // 交易发起人，必须是权限控制管理员，成为消息传输器的管理员
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // 权限控制账户，防止部署后被他人抢先初始化并控制验证人
+    #[account(
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        constraint = access_control.admin == authority.key() @ ErrorCode::UnauthorizedAdmin,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    // 消息传输器账户，通过PDA派生
+    #[account(
+        init,
+        payer = authority,
+        space = MessageTransmitter::LEN,
+        seeds = [b"message_transmitter"],
+        bump,
+    )]
+    pub message_transmitter: Account<'info, MessageTransmitter>,
+
+    // 系统程序
+    pub system_program: Program<'info, System>,
+}
+
+// 初始化消息传输器的处理函数
+pub fn initialize_message_transmitter(
+    ctx: Context<InitializeMessageTransmitter>,
+    params: InitializeMessageTransmitterParams,
+) -> Result<()> {
+    let message_transmitter = &mut ctx.accounts.message_transmitter;
+    message_transmitter.authority = ctx.accounts.authority.key();
+    message_transmitter.pending_authority = Pubkey::default();
+    message_transmitter.nonce = 0;
+    message_transmitter.paused = false;
+    message_transmitter.local_domain = params.local_domain;
+    message_transmitter.version = params.version;
+
+    // 启用初始验证人，签名阈值默认为1
+    message_transmitter.enable_attester(params.attester)?;
+    message_transmitter.set_signature_threshold(1)?;
+
+    emit!(AttesterEnabled {
+        attester: params.attester,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::error::ErrorCode;
+use super::burn_limits::DEFAULT_BURN_WINDOW_DURATION;
+use super::reclaim_burn_message::DEFAULT_BURN_MESSAGE_RECLAIM_DELAY;
+use super::state::*;
+
+// 初始化代币消息传输器的参数
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct InitializeTokenMessengerParams {
+    // 销毁消息体版本
+    pub message_body_version: u32,
+}
+
+// 初始化代币消息传输器的指令账户结构
+#[derive(Accounts)]
+pub struct InitializeTokenMessenger<'info> {
+    // 交易发起人，必须是消息传输器的管理员，同时成为代币消息传输器的管理员
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // 消息传输器账户，通过PDA派生
+    #[account(
+        seeds = [b"message_transmitter"],
+        bump,
+        has_one = authority @ ErrorCode::UnauthorizedAdmin,
+    )]
+    pub message_transmitter: Account<'info, MessageTransmitter>,
+
+    // 代币铸造账户
+    pub mint: Account<'info, Mint>,
+
+    // 代币消息传输器账户，通过PDA派生
+    #[account(
+        init,
+        payer = authority,
+        space = TokenMessenger::LEN,
+        seeds = [b"token_messenger", mint.key().as_ref()],
+        bump,
+    )]
+    pub token_messenger: Account<'info, TokenMessenger>,
+
+    // 系统程序
+    pub system_program: Program<'info, System>,
+}
+
+// 初始化代币消息传输器的处理函数
+pub fn initialize_token_messenger(
+    ctx: Context<InitializeTokenMessenger>,
+    params: InitializeTokenMessengerParams,
+) -> Result<()> {
+    let token_messenger = &mut ctx.accounts.token_messenger;
+    token_messenger.authority = ctx.accounts.authority.key();
+    token_messenger.pending_authority = Pubkey::default();
+    token_messenger.local_token = ctx.accounts.mint.key();
+    token_messenger.message_body_version = params.message_body_version;
+    token_messenger.max_burn_amount_per_message = 0;
+    token_messenger.burn_limit_per_window = 0;
+    token_messenger.burn_window_duration = DEFAULT_BURN_WINDOW_DURATION;
+    token_messenger.burned_in_window = 0;
+    token_messenger.burn_window_start = 0;
+    token_messenger.burn_message_reclaim_delay = DEFAULT_BURN_MESSAGE_RECLAIM_DELAY;
+
+    Ok(())
+}
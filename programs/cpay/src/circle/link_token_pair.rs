@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::error::ErrorCode;
+use super::state::*;
+
+// 关联代币对的指令账户结构
+#[derive(Accounts)]
+#[instruction(remote_domain: u32, remote_token: [u8; 32])]
+pub struct LinkTokenPair<'info> {
+    // 管理员，必须是本地代币消息传输器的 authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // 本地代币铸造账户
+    pub mint: Account<'info, Mint>,
+
+    // 本地代币消息传输器账户，通过PDA派生
+    #[account(
+        seeds = [b"token_messenger", mint.key().as_ref()],
+        bump,
+        has_one = authority @ ErrorCode::UnauthorizedAdmin,
+    )]
+    pub token_messenger: Account<'info, TokenMessenger>,
+
+    // 远端链域的远端代币消息传输器账户，只能为已注册的远端链域关联代币对
+    #[account(
+        seeds = [b"remote_token_messenger", remote_domain.to_le_bytes().as_ref()],
+        bump,
+        constraint = remote_token_messenger.domain == remote_domain @ ErrorCode::InvalidRemoteTokenMessenger,
+    )]
+    pub remote_token_messenger: Account<'info, RemoteTokenMessenger>,
+
+    // 代币对账户，按(远端链域ID, 远端代币地址)派生
+    #[account(
+        init,
+        payer = authority,
+        space = TokenPair::LEN,
+        seeds = [b"token_pair", remote_domain.to_le_bytes().as_ref(), remote_token.as_ref()],
+        bump,
+    )]
+    pub token_pair: Account<'info, TokenPair>,
+
+    // 系统程序
+    pub system_program: Program<'info, System>,
+}
+
+// 关联代币对的处理函数
+pub fn link_token_pair(
+    ctx: Context<LinkTokenPair>,
+    remote_domain: u32,             // 远端链域ID
+    remote_token: [u8; 32],         // 远端代币地址
+) -> Result<()> {
+    require!(remote_token != [0u8; 32], ErrorCode::InvalidTokenPair);
+
+    let token_pair = &mut ctx.accounts.token_pair;
+    token_pair.remote_domain = remote_domain;
+    token_pair.remote_token = remote_token;
+    token_pair.local_token = ctx.accounts.mint.key();
+
+    emit!(TokenPairLinked {
+        local_token: token_pair.local_token,
+        remote_domain,
+        remote_token,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+// CCTP v1 消息格式（大端序）
+//
+// Message:
+//   version            u32       [0, 4)
+//   source_domain      u32       [4, 8)
+//   destination_domain u32       [8, 12)
+//   nonce              u64       [12, 20)
+//   sender             bytes32   [20, 52)
+//   recipient          bytes32   [52, 84)
+//   destination_caller bytes32   [84, 116)
+//   message_body       bytes     [116, ..)
+//
+// BurnMessage (message_body):
+//   version            u32       [0, 4)
+//   burn_token         bytes32   [4, 36)
+//   mint_recipient     bytes32   [36, 68)
+//   amount             uint256   [68, 100)
+//   message_sender     bytes32   [100, 132)
+
+const VERSION_INDEX: usize = 0;
+const SOURCE_DOMAIN_INDEX: usize = 4;
+const DESTINATION_DOMAIN_INDEX: usize = 8;
+const NONCE_INDEX: usize = 12;
+const SENDER_INDEX: usize = 20;
+const RECIPIENT_INDEX: usize = 52;
+const DESTINATION_CALLER_INDEX: usize = 84;
+const MESSAGE_BODY_INDEX: usize = 116;
+
+const BURN_VERSION_INDEX: usize = 0;
+const BURN_TOKEN_INDEX: usize = 4;
+const MINT_RECIPIENT_INDEX: usize = 36;
+const AMOUNT_INDEX: usize = 68;
+const MESSAGE_SENDER_INDEX: usize = 100;
+const BURN_MESSAGE_LEN: usize = 132;
+
+// 跨链消息
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+    // 消息格式版本
+    pub version: u32,
+    // 源链域ID
+    pub source_domain: u32,
+    // 目标链域ID
+    pub destination_domain: u32,
+    // 消息序列号
+    pub nonce: u64,
+    // 源链发送方（TokenMessenger）
+    pub sender: [u8; 32],
+    // 目标链接收方（TokenMessenger）
+    pub recipient: [u8; 32],
+    // 目标链上允许接收消息的调用者，全零表示任何人
+    pub destination_caller: [u8; 32],
+    // 消息体
+    pub message_body: Vec<u8>,
+}
+
+impl Message {
+    // 消息头长度
+    pub const HEADER_LEN: usize = MESSAGE_BODY_INDEX;
+
+    // 解析消息字节
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        require!(data.len() >= Self::HEADER_LEN, ErrorCode::InvalidMessageLength);
+
+        Ok(Self {
+            version: read_u32(data, VERSION_INDEX),
+            source_domain: read_u32(data, SOURCE_DOMAIN_INDEX),
+            destination_domain: read_u32(data, DESTINATION_DOMAIN_INDEX),
+            nonce: read_u64(data, NONCE_INDEX),
+            sender: read_bytes32(data, SENDER_INDEX),
+            recipient: read_bytes32(data, RECIPIENT_INDEX),
+            destination_caller: read_bytes32(data, DESTINATION_CALLER_INDEX),
+            message_body: data[MESSAGE_BODY_INDEX..].to_vec(),
+        })
+    }
+
+    // 编码为消息字节
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::HEADER_LEN + self.message_body.len());
+        data.extend_from_slice(&self.version.to_be_bytes());
+        data.extend_from_slice(&self.source_domain.to_be_bytes());
+        data.extend_from_slice(&self.destination_domain.to_be_bytes());
+        data.extend_from_slice(&self.nonce.to_be_bytes());
+        data.extend_from_slice(&self.sender);
+        data.extend_from_slice(&self.recipient);
+        data.extend_from_slice(&self.destination_caller);
+        data.extend_from_slice(&self.message_body);
+        data
+    }
+
+    // 读取源链域ID，长度不足时返回0（仅用于账户种子，完整校验由 parse 完成）
+    pub fn source_domain_of(data: &[u8]) -> u32 {
+        if data.len() < Self::HEADER_LEN {
+            return 0;
+        }
+        read_u32(data, SOURCE_DOMAIN_INDEX)
+    }
+
+    // 读取消息序列号，长度不足时返回0（仅用于账户种子，完整校验由 parse 完成）
+    pub fn nonce_of(data: &[u8]) -> u64 {
+        if data.len() < Self::HEADER_LEN {
+            return 0;
+        }
+        read_u64(data, NONCE_INDEX)
+    }
+
+    // 读取销毁消息体中的源链代币地址，长度不足时返回全零（仅用于账户种子，完整校验由 parse 完成）
+    pub fn burn_token_of(data: &[u8]) -> [u8; 32] {
+        if data.len() < Self::HEADER_LEN + BurnMessageBody::LEN {
+            return [0u8; 32];
+        }
+        read_bytes32(data, MESSAGE_BODY_INDEX + BURN_TOKEN_INDEX)
+    }
+}
+
+// 销毁消息体
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BurnMessageBody {
+    // 消息体版本
+    pub version: u32,
+    // 源链上被销毁的代币地址
+    pub burn_token: [u8; 32],
+    // 目标链接收方地址
+    pub mint_recipient: [u8; 32],
+    // 代币数量（线上格式为uint256）
+    pub amount: u64,
+    // 源链上的销毁发起人
+    pub message_sender: [u8; 32],
+}
+
+impl BurnMessageBody {
+    pub const LEN: usize = BURN_MESSAGE_LEN;
+
+    // 解析消息体字节
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        require!(data.len() == Self::LEN, ErrorCode::InvalidMessageLength);
+
+        // uint256 的高24字节必须为0，否则数量超出 u64 范围
+        require!(
+            data[AMOUNT_INDEX..AMOUNT_INDEX + 24].iter().all(|byte| *byte == 0),
+            ErrorCode::MathOverflow
+        );
+
+        Ok(Self {
+            version: read_u32(data, BURN_VERSION_INDEX),
+            burn_token: read_bytes32(data, BURN_TOKEN_INDEX),
+            mint_recipient: read_bytes32(data, MINT_RECIPIENT_INDEX),
+            amount: read_u64(data, AMOUNT_INDEX + 24),
+            message_sender: read_bytes32(data, MESSAGE_SENDER_INDEX),
+        })
+    }
+
+    // 编码为消息体字节
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN);
+        data.extend_from_slice(&self.version.to_be_bytes());
+        data.extend_from_slice(&self.burn_token);
+        data.extend_from_slice(&self.mint_recipient);
+        data.extend_from_slice(&[0u8; 24]);
+        data.extend_from_slice(&self.amount.to_be_bytes());
+        data.extend_from_slice(&self.message_sender);
+        data
+    }
+}
+
+fn read_u32(data: &[u8], index: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&data[index..index + 4]);
+    u32::from_be_bytes(bytes)
+}
+
+fn read_u64(data: &[u8], index: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&data[index..index + 8]);
+    u64::from_be_bytes(bytes)
+}
+
+fn read_bytes32(data: &[u8], index: usize) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&data[index..index + 32]);
+    bytes
+}
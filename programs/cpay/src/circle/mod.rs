@@ -1,9 +1,45 @@
 // Circle CCTP (Cross-Chain Transfer Protocol) 模块
+pub mod accept_ownership;   // 接受管理员转移
+pub mod add_remote_token_messenger; // 添加远端代币消息传输器
+pub mod attester;           // 验证人签名校验
+pub mod burn_limits;        // 销毁限额
 pub mod deposit_for_burn;   // 销毁代币并创建跨链转账消息
+pub mod disable_attester;   // 禁用验证人
+pub mod enable_attester;    // 启用验证人
+pub mod initialize_message_transmitter; // 初始化消息传输器
+pub mod initialize_token_messenger;     // 初始化代币消息传输器
+pub mod link_token_pair;    // 关联代币对
+pub mod message;            // 跨链消息编解码
+pub mod pause_message_transmitter; // 暂停/恢复消息传输器
 pub mod receive_message;    // 接收跨链消息并铸造代币
+pub mod reclaim_burn_message; // 回收销毁消息账户租金
+pub mod remove_remote_token_messenger; // 移除远端代币消息传输器
+pub mod replace_deposit_for_burn; // 替换未投递的销毁消息
+pub mod set_burn_limits;    // 设置销毁限额
+pub mod set_signature_threshold; // 设置签名阈值
 pub mod state;             // 状态账户定义
+pub mod transfer_ownership; // 发起管理员转移
+pub mod unlink_token_pair;  // 解除代币对关联
 
 // 导出所有子模块内容
+pub use accept_ownership::*;
+pub use add_remote_token_messenger::*;
+pub use attester::*;
+pub use burn_limits::*;
 pub use deposit_for_burn::*;
+pub use disable_attester::*;
+pub use enable_attester::*;
+pub use initialize_message_transmitter::*;
+pub use initialize_token_messenger::*;
+pub use link_token_pair::*;
+pub use message::*;
+pub use pause_message_transmitter::*;
 pub use receive_message::*;
+pub use reclaim_burn_message::*;
+pub use remove_remote_token_messenger::*;
+pub use replace_deposit_for_burn::*;
+pub use set_burn_limits::*;
+pub use set_signature_threshold::*;
 pub use state::*;
+pub use transfer_ownership::*;
+pub use unlink_token_pair::*;
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::{AccessControl, Role};
+use super::state::*;
+
+// 暂停/恢复消息传输器的指令账户结构
+#[derive(Accounts)]
+pub struct PauseMessageTransmitter<'info> {
+    // 暂停者，必须拥有暂停者角色
+    pub authority: Signer<'info>,
+
+    // 权限控制账户，调用者必须拥有暂停者角色
+    #[account(
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        constraint = access_control.has_role(Role::Pauser, &authority.key()) @ ErrorCode::UnauthorizedRole,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    // 消息传输器账户，通过PDA派生
+    #[account(
+        mut,
+        seeds = [b"message_transmitter"],
+        bump,
+    )]
+    pub message_transmitter: Account<'info, MessageTransmitter>,
+}
+
+// 暂停消息传输器，暂停后 receive_message 不可用；程序级的暂停见 instructions::pause
+pub fn pause_message_transmitter(ctx: Context<PauseMessageTransmitter>) -> Result<()> {
+    ctx.accounts.message_transmitter.paused = true;
+
+    emit!(Paused {});
+
+    Ok(())
+}
+
+// 恢复消息传输器
+pub fn unpause_message_transmitter(ctx: Context<PauseMessageTransmitter>) -> Result<()> {
+    ctx.accounts.message_transmitter.paused = false;
+
+    emit!(Unpaused {});
+
+    Ok(())
+}
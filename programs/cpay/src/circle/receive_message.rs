@@ -1,14 +1,26 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 use crate::error::ErrorCode;
+use crate::instructions::GlobalState;
+use crate::state::*;
+use super::message::*;
 use super::state::*;
 
 // 接收跨链消息并铸造代币的指令账户结构
 #[derive(Accounts)]
+#[instruction(message: Vec<u8>)]
 pub struct ReceiveMessage<'info> {
-    // 交易发起人，必须签名
+    // 交易发起人，必须拥有中继者角色
     #[account(mut)]
     pub authority: Signer<'info>,
+
+    // 权限控制账户，调用者必须拥有中继者角色
+    #[account(
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        constraint = access_control.has_role(Role::Relayer, &authority.key()) @ ErrorCode::UnauthorizedRole,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
     
     // 消息传输器账户，通过PDA派生
     #[account(
@@ -17,6 +29,54 @@ pub struct ReceiveMessage<'info> {
         bump,
     )]
     pub message_transmitter: Account<'info, MessageTransmitter>,
+
+    // 源链的远端代币消息传输器账户，消息发送方必须与之一致
+    #[account(
+        seeds = [b"remote_token_messenger", Message::source_domain_of(&message).to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub remote_token_messenger: Account<'info, RemoteTokenMessenger>,
+
+    // 代币对账户，由(源链域ID, 源链代币地址)解析出本地代币
+    #[account(
+        seeds = [
+            b"token_pair",
+            Message::source_domain_of(&message).to_le_bytes().as_ref(),
+            Message::burn_token_of(&message).as_ref(),
+        ],
+        bump,
+        constraint = token_pair.local_token == mint.key() @ ErrorCode::InvalidTokenPair,
+    )]
+    pub token_pair: Account<'info, TokenPair>,
+
+    // 本地代币消息传输器账户，提供销毁消息体版本
+    #[account(
+        seeds = [b"token_messenger", mint.key().as_ref()],
+        bump,
+    )]
+    pub token_messenger: Account<'info, TokenMessenger>,
+
+    // 已使用序列号账户，按(源链域ID, 序列号区间)派生，用于防止消息重放
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = UsedNonces::LEN,
+        seeds = [
+            b"used_nonces",
+            Message::source_domain_of(&message).to_le_bytes().as_ref(),
+            UsedNonces::first_nonce(Message::nonce_of(&message)).to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub used_nonces: Box<Account<'info, UsedNonces>>,
+
+    // 全局状态账户，汇总所有金库的资金并保存程序级暂停标志
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
     
     // 程序的USDC代币账户，通过PDA派生
     #[account(
@@ -25,6 +85,14 @@ pub struct ReceiveMessage<'info> {
         bump,
     )]
     pub program_token_account: Account<'info, TokenAccount>,
+
+    // 资金账户
+    #[account(
+        mut,
+        seeds = [b"fund_account", program_token_account.key().as_ref()],
+        bump,
+    )]
+    pub fund_account: Account<'info, FundAccount>,
     
     // USDC代币铸造账户
     #[account(mut)]
@@ -36,34 +104,67 @@ pub struct ReceiveMessage<'info> {
     pub system_program: Program<'info, System>,
 }
 
-// 跨链消息数据结构
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub struct MessageData {
-    // 源链域ID
-    pub source_domain: u32,
-    // 发送方地址
-    pub sender: [u8; 32],
-    // 接收方地址
-    pub recipient: Pubkey,
-    // 代币数量
-    pub amount: u64,
-    // 消息体数据
-    pub message_body: Vec<u8>,
-}
-
 // 接收跨链消息并铸造代币的处理函数
 pub fn receive_message(
     ctx: Context<ReceiveMessage>, 
-    message: MessageData,           // 跨链消息数据
+    message: Vec<u8>,               // CCTP v1 格式的消息字节
     signatures: Vec<[u8; 65]>,      // 验证人签名列表
 ) -> Result<()> {
+    // 验证程序级接收操作未暂停
+    ctx.accounts.global_state.require_not_paused(GlobalState::PAUSE_RECEIVE)?;
+
     let message_transmitter = &mut ctx.accounts.message_transmitter;
     
     // 验证消息传输器未暂停
     require!(!message_transmitter.paused, ErrorCode::TransmitterPaused);
     
-    // 验证签名列表非空（在生产环境中，这里需要验证签名是否来自授权验证人）
-    require!(!signatures.is_empty(), ErrorCode::InvalidSignatures);
+    // 验证签名来自已启用的验证人且数量满足阈值
+    message_transmitter.verify_attestation_signatures(&message, &signatures)?;
+
+    // 解析消息并校验消息头
+    let message = Message::parse(&message)?;
+    require!(
+        message.version == message_transmitter.version,
+        ErrorCode::InvalidMessageVersion
+    );
+    require!(
+        message.destination_domain == message_transmitter.local_domain,
+        ErrorCode::InvalidDestinationDomain
+    );
+    require!(
+        message.sender == ctx.accounts.remote_token_messenger.token_messenger,
+        ErrorCode::InvalidRemoteTokenMessenger
+    );
+    require!(
+        message.recipient == crate::ID.to_bytes(),
+        ErrorCode::InvalidMessageRecipient
+    );
+    require!(
+        message.destination_caller == [0u8; 32]
+            || message.destination_caller == ctx.accounts.authority.key().to_bytes(),
+        ErrorCode::InvalidDestinationCaller
+    );
+
+    // 解析销毁消息体，接收方必须是程序的代币账户
+    let burn_message = BurnMessageBody::parse(&message.message_body)?;
+    require!(
+        burn_message.version == ctx.accounts.token_messenger.message_body_version,
+        ErrorCode::InvalidMessageBodyVersion
+    );
+    require!(
+        burn_message.burn_token == ctx.accounts.token_pair.remote_token,
+        ErrorCode::InvalidTokenPair
+    );
+    require!(
+        burn_message.mint_recipient == ctx.accounts.program_token_account.key().to_bytes(),
+        ErrorCode::InvalidMintRecipient
+    );
+
+    // 检查并标记消息序列号，重复投递的消息会在这里失败
+    let used_nonces = &mut ctx.accounts.used_nonces;
+    used_nonces.remote_domain = message.source_domain;
+    used_nonces.first_nonce = UsedNonces::first_nonce(message.nonce);
+    used_nonces.use_nonce(message.nonce)?;
     
     // 准备签名者的种子
     let signer_seeds: &[&[&[u8]]] = &[&[
@@ -89,7 +190,33 @@ pub fn receive_message(
     );
     
     // 执行代币铸造到程序的PDA token account
-    token::mint_to(mint_to_ctx, message.amount)?;
+    token::mint_to(mint_to_ctx, burn_message.amount)?;
+
+    // 更新资金账户状态
+    let fund_account = &mut ctx.accounts.fund_account;
+    fund_account.idle_amount = fund_account.idle_amount.checked_add(burn_message.amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    fund_account.total_received_amount = fund_account.total_received_amount.checked_add(burn_message.amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    fund_account.last_update_time = Clock::get()?.unix_timestamp;
+    ctx.accounts.global_state.record_received(burn_message.amount)?;
+
+    // 发出跨链资金接收事件
+    emit!(CrossChainFundReceived {
+        amount: burn_message.amount,
+        source_chain: message.source_domain,
+        sender: burn_message.message_sender,
+        timestamp: fund_account.last_update_time,
+    });
+
+    // 发出资金状态变更事件
+    emit!(FundStatusChanged {
+        amount: burn_message.amount,
+        from_status: FundStatus::Received,  // 从跨链转入状态
+        to_status: FundStatus::Idle,        // 变为闲置状态
+        token_account: ctx.accounts.program_token_account.key(),
+        timestamp: fund_account.last_update_time,
+    });
     
     Ok(())
 }
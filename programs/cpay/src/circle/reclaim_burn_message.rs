@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use super::state::*;
+
+// 默认销毁消息回收等待时间（7天）
+pub const DEFAULT_BURN_MESSAGE_RECLAIM_DELAY: i64 = 7 * 24 * 60 * 60;
+
+// 回收销毁消息账户的指令账户结构
+#[derive(Accounts)]
+pub struct ReclaimBurnMessage<'info> {
+    // 原租金支付人，接收退还的租金
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // 代币消息传输器账户，通过PDA派生
+    #[account(
+        seeds = [b"token_messenger", burn_message.mint.as_ref()],
+        bump,
+    )]
+    pub token_messenger: Account<'info, TokenMessenger>,
+
+    // 要回收的销毁消息账户
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"burn_message", burn_message.nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = payer @ ErrorCode::InvalidArgument,
+    )]
+    pub burn_message: Account<'info, BurnMessage>,
+}
+
+// 设置销毁消息回收等待时间的指令账户结构
+#[derive(Accounts)]
+pub struct SetBurnMessageReclaimDelay<'info> {
+    // 管理员，必须是代币消息传输器的 authority
+    pub authority: Signer<'info>,
+
+    // 代币消息传输器账户
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::UnauthorizedAdmin,
+    )]
+    pub token_messenger: Account<'info, TokenMessenger>,
+}
+
+// 关闭销毁消息账户并退还租金，账户必须已超过等待时间
+// 验证人签名只能证明消息已被签名，不能证明已在目标链接收，因此不提供凭签名提前回收的途径
+pub fn reclaim_burn_message(ctx: Context<ReclaimBurnMessage>) -> Result<()> {
+    let burn_message = &ctx.accounts.burn_message;
+
+    let now = Clock::get()?.unix_timestamp;
+    let reclaimable_at = burn_message
+        .created_at
+        .checked_add(ctx.accounts.token_messenger.burn_message_reclaim_delay)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(now >= reclaimable_at, ErrorCode::BurnMessageReclaimTooEarly);
+
+    emit!(BurnMessageReclaimed {
+        nonce: burn_message.nonce,
+        payer: burn_message.payer,
+    });
+
+    Ok(())
+}
+
+// 设置销毁消息回收等待时间
+pub fn set_burn_message_reclaim_delay(
+    ctx: Context<SetBurnMessageReclaimDelay>,
+    burn_message_reclaim_delay: i64,    // 新的等待时间（秒）
+) -> Result<()> {
+    require!(burn_message_reclaim_delay >= 0, ErrorCode::InvalidArgument);
+
+    let token_messenger = &mut ctx.accounts.token_messenger;
+    token_messenger.burn_message_reclaim_delay = burn_message_reclaim_delay;
+
+    emit!(BurnMessageReclaimDelayUpdated {
+        local_token: token_messenger.local_token,
+        burn_message_reclaim_delay,
+    });
+
+    Ok(())
+}
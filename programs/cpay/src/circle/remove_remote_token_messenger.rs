@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use super::state::*;
+
+// 移除远端代币消息传输器的指令账户结构
+#[derive(Accounts)]
+pub struct RemoveRemoteTokenMessenger<'info> {
+    // 管理员，必须是消息传输器的 authority，接收关闭账户退还的租金
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // 消息传输器账户，通过PDA派生
+    #[account(
+        seeds = [b"message_transmitter"],
+        bump,
+        has_one = authority @ ErrorCode::UnauthorizedAdmin,
+    )]
+    pub message_transmitter: Account<'info, MessageTransmitter>,
+
+    // 远端代币消息传输器账户，移除后关闭
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"remote_token_messenger", remote_token_messenger.domain.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub remote_token_messenger: Account<'info, RemoteTokenMessenger>,
+}
+
+// 移除远端代币消息传输器的处理函数
+pub fn remove_remote_token_messenger(ctx: Context<RemoveRemoteTokenMessenger>) -> Result<()> {
+    let remote_token_messenger = &ctx.accounts.remote_token_messenger;
+
+    emit!(RemoteTokenMessengerRemoved {
+        domain: remote_token_messenger.domain,
+        token_messenger: remote_token_messenger.token_messenger,
+    });
+
+    Ok(())
+}
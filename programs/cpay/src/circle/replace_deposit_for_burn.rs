@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::instructions::GlobalState;
+use crate::state::{AccessControl, Role};
+use super::deposit_for_burn::build_message;
+use super::state::*;
+
+// 替换未投递销毁消息的指令账户结构
+#[derive(Accounts)]
+pub struct ReplaceDepositForBurn<'info> {
+    // 原销毁发起人，必须仍然拥有操作员角色
+    pub authority: Signer<'info>,
+
+    // 权限控制账户，调用者必须拥有操作员角色
+    #[account(
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        constraint = access_control.has_role(Role::Operator, &authority.key()) @ ErrorCode::UnauthorizedRole,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    // 全局状态账户，保存程序级暂停标志
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    // 消息传输器账户，提供本链域ID和消息版本
+    #[account(
+        seeds = [b"message_transmitter"],
+        bump,
+    )]
+    pub message_transmitter: Account<'info, MessageTransmitter>,
+
+    // 代币消息传输器账户，通过PDA派生
+    #[account(
+        seeds = [b"token_messenger", burn_message.mint.as_ref()],
+        bump,
+    )]
+    pub token_messenger: Account<'info, TokenMessenger>,
+
+    // 目标链的远端代币消息传输器账户
+    #[account(
+        seeds = [b"remote_token_messenger", burn_message.remote_domain.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub remote_token_messenger: Account<'info, RemoteTokenMessenger>,
+
+    // 要替换的销毁消息账户
+    #[account(
+        mut,
+        seeds = [b"burn_message", burn_message.nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ ErrorCode::InvalidMessageSender,
+    )]
+    pub burn_message: Account<'info, BurnMessage>,
+}
+
+// 替换销毁消息的接收方和目标链调用者，重新发出同一序列号的跨链消息
+pub fn replace_deposit_for_burn(
+    ctx: Context<ReplaceDepositForBurn>,
+    new_mint_recipient: [u8; 32],       // 新接收方地址
+    new_destination_caller: [u8; 32],   // 新目标链调用者，全零表示任何人
+) -> Result<()> {
+    // 替换会重新发出跨链消息，跨链转出暂停时同样不可用
+    ctx.accounts.global_state.require_not_paused(GlobalState::PAUSE_BURN)?;
+    require!(new_mint_recipient != [0u8; 32], ErrorCode::InvalidMintRecipient);
+
+    let burn_message = &mut ctx.accounts.burn_message;
+    let original_mint_recipient = burn_message.recipient;
+    let original_destination_caller = burn_message.destination_caller;
+
+    burn_message.recipient = new_mint_recipient;
+    burn_message.destination_caller = new_destination_caller;
+    burn_message.version = burn_message.version.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+    let message = build_message(
+        &ctx.accounts.message_transmitter,
+        &ctx.accounts.token_messenger,
+        &ctx.accounts.remote_token_messenger,
+        burn_message,
+    );
+
+    // 发出替换后的跨链消息，中继器使用新消息重新获取验证人签名
+    emit!(MessageSent {
+        message: message.serialize(),
+    });
+
+    emit!(DepositForBurnReplaced {
+        nonce: burn_message.nonce,
+        original_mint_recipient,
+        original_destination_caller,
+        new_mint_recipient,
+        new_destination_caller,
+        version: burn_message.version,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use super::state::*;
+
+// 设置销毁限额的参数
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct SetBurnLimitsParams {
+    // 单条消息最大销毁数量，0表示不限制
+    pub max_burn_amount_per_message: u64,
+    // 时间窗口内的最大销毁数量，0表示不限制
+    pub burn_limit_per_window: u64,
+    // 时间窗口时长（秒）
+    pub burn_window_duration: i64,
+}
+
+// 设置销毁限额的指令账户结构
+#[derive(Accounts)]
+pub struct SetBurnLimits<'info> {
+    // 管理员，必须是代币消息传输器的 authority
+    pub authority: Signer<'info>,
+
+    // 代币消息传输器账户
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::UnauthorizedAdmin,
+    )]
+    pub token_messenger: Account<'info, TokenMessenger>,
+}
+
+// 设置销毁限额的处理函数
+pub fn set_burn_limits(ctx: Context<SetBurnLimits>, params: SetBurnLimitsParams) -> Result<()> {
+    let token_messenger = &mut ctx.accounts.token_messenger;
+    token_messenger.set_burn_limits(
+        params.max_burn_amount_per_message,
+        params.burn_limit_per_window,
+        params.burn_window_duration,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    emit!(BurnLimitsUpdated {
+        local_token: token_messenger.local_token,
+        max_burn_amount_per_message: params.max_burn_amount_per_message,
+        burn_limit_per_window: params.burn_limit_per_window,
+        burn_window_duration: params.burn_window_duration,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use super::state::*;
+
+// 设置签名阈值的指令账户结构
+#[derive(Accounts)]
+pub struct SetSignatureThreshold<'info> {
+    // 管理员，必须是消息传输器的 authority
+    pub authority: Signer<'info>,
+
+    // 消息传输器账户，通过PDA派生
+    #[account(
+        mut,
+        seeds = [b"message_transmitter"],
+        bump,
+        has_one = authority @ ErrorCode::UnauthorizedAdmin,
+    )]
+    pub message_transmitter: Account<'info, MessageTransmitter>,
+}
+
+// 设置签名阈值的处理函数
+pub fn set_signature_threshold(
+    ctx: Context<SetSignatureThreshold>,
+    new_signature_threshold: u32,   // 新的签名阈值
+) -> Result<()> {
+    let message_transmitter = &mut ctx.accounts.message_transmitter;
+    let old_signature_threshold = message_transmitter.signature_threshold;
+    message_transmitter.set_signature_threshold(new_signature_threshold)?;
+
+    emit!(SignatureThresholdUpdated {
+        old_signature_threshold,
+        new_signature_threshold,
+    });
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
 
 // 消息传输器账户，用于跨链消息的验证和处理
 #[account]
@@ -6,10 +7,35 @@ use anchor_lang::prelude::*;
 pub struct MessageTransmitter {
     // 管理员权限地址
     pub authority: Pubkey,
-    // 消息序列号
+    // 待接受的新管理员地址（两步转移）
+    pub pending_authority: Pubkey,
+    // 消息序列号，本链域所有代币的跨链消息共用
     pub nonce: u64,
     // 是否暂停服务
     pub paused: bool,
+    // 本链域ID
+    pub local_domain: u32,
+    // 消息格式版本
+    pub version: u32,
+    // 签名阈值，receive_message 需要的验证人签名数量
+    pub signature_threshold: u32,
+    // 已启用的验证人（以太坊地址，左侧补零到32字节）
+    pub enabled_attesters: Vec<Pubkey>,
+}
+
+impl MessageTransmitter {
+    // 最多可启用的验证人数量
+    pub const MAX_ATTESTERS: usize = 16;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        32 + // pending_authority
+        8 + // nonce
+        1 + // paused
+        4 + // local_domain
+        4 + // version
+        4 + // signature_threshold
+        4 + 32 * Self::MAX_ATTESTERS; // enabled_attesters
 }
 
 // 代币消息传输器账户，用于处理特定代币的跨链传输
@@ -18,12 +44,73 @@ pub struct MessageTransmitter {
 pub struct TokenMessenger {
     // 管理员权限地址
     pub authority: Pubkey,
+    // 待接受的新管理员地址（两步转移）
+    pub pending_authority: Pubkey,
     // 本地代币地址
     pub local_token: Pubkey,
+    // 销毁消息体版本
+    pub message_body_version: u32,
+    // 单条消息最大销毁数量，0表示不限制
+    pub max_burn_amount_per_message: u64,
+    // 时间窗口内的最大销毁数量，0表示不限制
+    pub burn_limit_per_window: u64,
+    // 时间窗口时长（秒）
+    pub burn_window_duration: i64,
+    // 当前窗口内已销毁数量
+    pub burned_in_window: u64,
+    // 当前窗口开始时间
+    pub burn_window_start: i64,
+    // 销毁消息账户创建后可被回收的等待时间（秒）
+    pub burn_message_reclaim_delay: i64,
+}
+
+impl TokenMessenger {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        32 + // pending_authority
+        32 + // local_token
+        4 + // message_body_version
+        8 + // max_burn_amount_per_message
+        8 + // burn_limit_per_window
+        8 + // burn_window_duration
+        8 + // burned_in_window
+        8 + // burn_window_start
+        8; // burn_message_reclaim_delay
+}
+
+// 远端代币消息传输器账户，每个目标链域一个，记录该链上的 TokenMessenger 地址
+#[account]
+#[derive(Default)]
+pub struct RemoteTokenMessenger {
     // 目标链域ID
+    pub domain: u32,
+    // 目标链上的 TokenMessenger 地址
+    pub token_messenger: [u8; 32],
+}
+
+impl RemoteTokenMessenger {
+    pub const LEN: usize = 8 + // discriminator
+        4 + // domain
+        32; // token_messenger
+}
+
+// 代币对账户，将(远端链域ID, 远端代币地址)映射到本地代币
+#[account]
+#[derive(Default)]
+pub struct TokenPair {
+    // 远端链域ID
     pub remote_domain: u32,
-    // 消息序列号
-    pub nonce: u64,
+    // 远端代币地址
+    pub remote_token: [u8; 32],
+    // 本地代币铸造地址
+    pub local_token: Pubkey,
+}
+
+impl TokenPair {
+    pub const LEN: usize = 8 + // discriminator
+        4 + // remote_domain
+        32 + // remote_token
+        32; // local_token
 }
 
 // 销毁代币消息账户，记录跨链转账时的代币销毁信息
@@ -40,4 +127,224 @@ pub struct BurnMessage {
     pub recipient: [u8; 32],
     // 消息序列号
     pub nonce: u64,
+    // 销毁发起人，只有发起人可以替换接收方和目标链调用者
+    pub authority: Pubkey,
+    // 目标链上允许接收消息的调用者，全零表示任何人
+    pub destination_caller: [u8; 32],
+    // 消息版本号，每次替换加1
+    pub version: u32,
+    // 支付账户租金的地址，回收时退还租金
+    pub payer: Pubkey,
+    // 创建时间
+    pub created_at: i64,
+}
+
+// 已使用的消息序列号账户，每个账户按位记录一个源链域上一段连续序列号的使用情况
+#[account]
+pub struct UsedNonces {
+    // 源链域ID
+    pub remote_domain: u32,
+    // 本账户覆盖的第一个序列号
+    pub first_nonce: u64,
+    // 序列号使用位图（MAX_NONCES / 64 个u64）
+    pub used_nonces: [u64; 100],
+}
+
+impl UsedNonces {
+    // 每个账户覆盖的序列号数量
+    pub const MAX_NONCES: u64 = 6400;
+    // 位图所需的u64数量
+    pub const NONCES_WORDS: usize = (Self::MAX_NONCES / 64) as usize;
+
+    pub const LEN: usize = 8 + // discriminator
+        4 + // remote_domain
+        8 + // first_nonce
+        8 * Self::NONCES_WORDS; // used_nonces
+
+    // 计算序列号所在账户的第一个序列号
+    pub fn first_nonce(nonce: u64) -> u64 {
+        nonce - nonce % Self::MAX_NONCES
+    }
+
+    // 计算序列号在位图中的位置
+    fn nonce_position(&self, nonce: u64) -> Result<(usize, u64)> {
+        require!(
+            nonce >= self.first_nonce && nonce - self.first_nonce < Self::MAX_NONCES,
+            ErrorCode::InvalidNonce
+        );
+        let offset = nonce - self.first_nonce;
+        Ok(((offset / 64) as usize, 1u64 << (offset % 64)))
+    }
+
+    // 判断序列号是否已使用
+    pub fn is_nonce_used(&self, nonce: u64) -> Result<bool> {
+        let (word, mask) = self.nonce_position(nonce)?;
+        Ok(self.used_nonces[word] & mask != 0)
+    }
+
+    // 标记序列号为已使用，重复使用时报错
+    pub fn use_nonce(&mut self, nonce: u64) -> Result<()> {
+        let (word, mask) = self.nonce_position(nonce)?;
+        require!(self.used_nonces[word] & mask == 0, ErrorCode::NonceAlreadyUsed);
+        self.used_nonces[word] |= mask;
+        Ok(())
+    }
+}
+
+impl Default for UsedNonces {
+    fn default() -> Self {
+        Self {
+            remote_domain: 0,
+            first_nonce: 0,
+            used_nonces: [0; Self::NONCES_WORDS],
+        }
+    }
+}
+
+// 跨链消息发送事件，携带可供验证人签名的完整消息字节
+#[event]
+pub struct MessageSent {
+    // CCTP v1 格式的消息字节
+    pub message: Vec<u8>,
+}
+
+// 验证人启用事件
+#[event]
+pub struct AttesterEnabled {
+    // 验证人地址
+    pub attester: Pubkey,
+}
+
+// 验证人禁用事件
+#[event]
+pub struct AttesterDisabled {
+    // 验证人地址
+    pub attester: Pubkey,
+}
+
+// 签名阈值变更事件
+#[event]
+pub struct SignatureThresholdUpdated {
+    // 原阈值
+    pub old_signature_threshold: u32,
+    // 新阈值
+    pub new_signature_threshold: u32,
+}
+
+// 消息传输器暂停事件
+#[event]
+pub struct Paused {}
+
+// 消息传输器恢复事件
+#[event]
+pub struct Unpaused {}
+
+// 管理员转移发起事件
+#[event]
+pub struct OwnershipTransferStarted {
+    // 被转移的账户（MessageTransmitter 或 TokenMessenger）
+    pub account: Pubkey,
+    // 当前管理员
+    pub previous_authority: Pubkey,
+    // 待接受的新管理员
+    pub new_authority: Pubkey,
+}
+
+// 管理员转移完成事件
+#[event]
+pub struct OwnershipTransferred {
+    // 被转移的账户（MessageTransmitter 或 TokenMessenger）
+    pub account: Pubkey,
+    // 原管理员
+    pub previous_authority: Pubkey,
+    // 新管理员
+    pub new_authority: Pubkey,
+}
+
+// 远端代币消息传输器添加事件
+#[event]
+pub struct RemoteTokenMessengerAdded {
+    // 目标链域ID
+    pub domain: u32,
+    // 目标链上的 TokenMessenger 地址
+    pub token_messenger: [u8; 32],
+}
+
+// 远端代币消息传输器移除事件
+#[event]
+pub struct RemoteTokenMessengerRemoved {
+    // 目标链域ID
+    pub domain: u32,
+    // 目标链上的 TokenMessenger 地址
+    pub token_messenger: [u8; 32],
+}
+
+// 代币对关联事件
+#[event]
+pub struct TokenPairLinked {
+    // 本地代币铸造地址
+    pub local_token: Pubkey,
+    // 远端链域ID
+    pub remote_domain: u32,
+    // 远端代币地址
+    pub remote_token: [u8; 32],
+}
+
+// 代币对解除关联事件
+#[event]
+pub struct TokenPairUnlinked {
+    // 本地代币铸造地址
+    pub local_token: Pubkey,
+    // 远端链域ID
+    pub remote_domain: u32,
+    // 远端代币地址
+    pub remote_token: [u8; 32],
+}
+
+// 销毁限额变更事件
+#[event]
+pub struct BurnLimitsUpdated {
+    // 本地代币铸造地址
+    pub local_token: Pubkey,
+    // 单条消息最大销毁数量
+    pub max_burn_amount_per_message: u64,
+    // 时间窗口内的最大销毁数量
+    pub burn_limit_per_window: u64,
+    // 时间窗口时长（秒）
+    pub burn_window_duration: i64,
+}
+
+// 销毁消息替换事件
+#[event]
+pub struct DepositForBurnReplaced {
+    // 消息序列号
+    pub nonce: u64,
+    // 原接收方地址
+    pub original_mint_recipient: [u8; 32],
+    // 原目标链调用者
+    pub original_destination_caller: [u8; 32],
+    // 新接收方地址
+    pub new_mint_recipient: [u8; 32],
+    // 新目标链调用者
+    pub new_destination_caller: [u8; 32],
+    // 替换后的消息版本号
+    pub version: u32,
+}
+
+// 销毁消息回收等待时间变更事件
+#[event]
+pub struct BurnMessageReclaimDelayUpdated {
+    // 本地代币铸造地址
+    pub local_token: Pubkey,
+    // 新的等待时间（秒）
+    pub burn_message_reclaim_delay: i64,
+}
+
+// 销毁消息账户回收事件
+#[event]
+pub struct BurnMessageReclaimed {
+    // 消息序列号
+    pub nonce: u64,
+    // 退还租金的地址
+    pub payer: Pubkey,
 }
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use super::state::*;
+
+// 发起消息传输器管理员转移的指令账户结构
+#[derive(Accounts)]
+pub struct TransferOwnership<'info> {
+    // 当前管理员
+    pub authority: Signer<'info>,
+
+    // 消息传输器账户，通过PDA派生
+    #[account(
+        mut,
+        seeds = [b"message_transmitter"],
+        bump,
+        has_one = authority @ ErrorCode::UnauthorizedAdmin,
+    )]
+    pub message_transmitter: Account<'info, MessageTransmitter>,
+}
+
+// 发起代币消息传输器管理员转移的指令账户结构
+#[derive(Accounts)]
+pub struct TransferTokenMessengerOwnership<'info> {
+    // 当前管理员
+    pub authority: Signer<'info>,
+
+    // 代币消息传输器账户
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::UnauthorizedAdmin,
+    )]
+    pub token_messenger: Account<'info, TokenMessenger>,
+}
+
+// 发起消息传输器管理员转移，新管理员需要调用 accept_ownership 接受
+pub fn transfer_ownership(ctx: Context<TransferOwnership>, new_authority: Pubkey) -> Result<()> {
+    require!(new_authority != Pubkey::default(), ErrorCode::InvalidArgument);
+
+    let message_transmitter = &mut ctx.accounts.message_transmitter;
+    message_transmitter.pending_authority = new_authority;
+
+    emit!(OwnershipTransferStarted {
+        account: message_transmitter.key(),
+        previous_authority: message_transmitter.authority,
+        new_authority,
+    });
+
+    Ok(())
+}
+
+// 发起代币消息传输器管理员转移，新管理员需要调用 accept_token_messenger_ownership 接受
+pub fn transfer_token_messenger_ownership(
+    ctx: Context<TransferTokenMessengerOwnership>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    require!(new_authority != Pubkey::default(), ErrorCode::InvalidArgument);
+
+    let token_messenger = &mut ctx.accounts.token_messenger;
+    token_messenger.pending_authority = new_authority;
+
+    emit!(OwnershipTransferStarted {
+        account: token_messenger.key(),
+        previous_authority: token_messenger.authority,
+        new_authority,
+    });
+
+    Ok(())
+}
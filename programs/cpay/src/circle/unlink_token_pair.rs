@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use super::state::*;
+
+// 解除代币对关联的指令账户结构
+#[derive(Accounts)]
+pub struct UnlinkTokenPair<'info> {
+    // 管理员，必须是本地代币消息传输器的 authority，接收关闭账户退还的租金
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // 本地代币消息传输器账户，通过PDA派生
+    #[account(
+        seeds = [b"token_messenger", token_pair.local_token.as_ref()],
+        bump,
+        has_one = authority @ ErrorCode::UnauthorizedAdmin,
+    )]
+    pub token_messenger: Account<'info, TokenMessenger>,
+
+    // 代币对账户，解除关联后关闭
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            b"token_pair",
+            token_pair.remote_domain.to_le_bytes().as_ref(),
+            token_pair.remote_token.as_ref(),
+        ],
+        bump,
+    )]
+    pub token_pair: Account<'info, TokenPair>,
+}
+
+// 解除代币对关联的处理函数
+pub fn unlink_token_pair(ctx: Context<UnlinkTokenPair>) -> Result<()> {
+    let token_pair = &ctx.accounts.token_pair;
+
+    emit!(TokenPairUnlinked {
+        local_token: token_pair.local_token,
+        remote_domain: token_pair.remote_domain,
+        remote_token: token_pair.remote_token,
+    });
+
+    Ok(())
+}
@@ -224,4 +224,168 @@ pub enum ErrorCode {
 
     #[msg("Invalid message signatures")]
     InvalidSignatures,
+
+    // 签名数量与阈值不符错误
+    #[msg("Invalid attestation length")]
+    InvalidAttestationLength,
+
+    // 签名未按验证人地址排序或存在重复错误
+    #[msg("Invalid signature order or dupe")]
+    InvalidSignatureOrderOrDupe,
+
+    // 验证人未启用或地址无效错误
+    #[msg("Invalid attester")]
+    InvalidAttester,
+
+    // 验证人已启用错误
+    #[msg("Attester already enabled")]
+    AttesterAlreadyEnabled,
+
+    // 验证人未启用错误
+    #[msg("Attester not enabled")]
+    AttesterNotEnabled,
+
+    // 验证人数量超过上限错误
+    #[msg("Too many attesters")]
+    TooManyAttesters,
+
+    // 启用的验证人数量过少错误
+    #[msg("Too few enabled attesters")]
+    TooFewEnabledAttesters,
+
+    // 无效签名阈值错误
+    #[msg("Invalid signature threshold")]
+    InvalidSignatureThreshold,
+
+    // 消息序列号已被使用错误（重复投递）
+    #[msg("Nonce already used")]
+    NonceAlreadyUsed,
+
+    // 序列号不属于该账户范围错误
+    #[msg("Invalid nonce")]
+    InvalidNonce,
+
+    // 消息长度错误
+    #[msg("Invalid message length")]
+    InvalidMessageLength,
+
+    // 消息版本错误
+    #[msg("Invalid message version")]
+    InvalidMessageVersion,
+
+    // 消息目标链域ID与本链不符错误
+    #[msg("Invalid destination domain")]
+    InvalidDestinationDomain,
+
+    // 交易发起人不是消息指定的目标链调用者错误
+    #[msg("Invalid destination caller")]
+    InvalidDestinationCaller,
+
+    // 消息接收方与程序代币账户不符错误
+    #[msg("Invalid mint recipient")]
+    InvalidMintRecipient,
+
+    // 签名者不是待接受的新管理员错误
+    #[msg("Invalid pending authority")]
+    InvalidPendingAuthority,
+
+    // 远端代币消息传输器未注册或与消息发送方不符错误
+    #[msg("Invalid remote token messenger")]
+    InvalidRemoteTokenMessenger,
+
+    // 代币对未注册或与本地代币不符错误
+    #[msg("Invalid token pair")]
+    InvalidTokenPair,
+
+    // 销毁数量超过单条消息限额错误
+    #[msg("Burn amount exceeds per-message limit")]
+    BurnAmountExceedsMessageLimit,
+
+    // 销毁数量超过时间窗口限额错误
+    #[msg("Burn amount exceeds window limit")]
+    BurnAmountExceedsWindowLimit,
+
+    // 签名者不是销毁消息的发起人错误
+    #[msg("Invalid message sender")]
+    InvalidMessageSender,
+
+    // 销毁消息账户未到回收时间错误
+    #[msg("Burn message cannot be reclaimed yet")]
+    BurnMessageReclaimTooEarly,
+
+    // 金库已存在错误
+    #[msg("Treasury already exists for this mint")]
+    TreasuryAlreadyExists,
+
+    // 金库数量超过上限错误
+    #[msg("Too many treasuries")]
+    TooManyTreasuries,
+
+    // 调用者没有所需角色错误
+    #[msg("Signer does not have the required role")]
+    UnauthorizedRole,
+
+    // 角色已授予错误
+    #[msg("Role already granted")]
+    RoleAlreadyGranted,
+
+    // 角色未授予错误
+    #[msg("Role not granted")]
+    RoleNotGranted,
+
+    // 角色成员数量超过上限错误
+    #[msg("Too many role members")]
+    TooManyRoleMembers,
+
+    // 操作已暂停错误
+    #[msg("Operation is paused")]
+    OperationPaused,
+
+    // 金库未配置分配目标错误
+    #[msg("Vault has no allocation")]
+    AllocationNotFound,
+
+    // 分配权重之和超过100%错误
+    #[msg("Total allocation weight exceeds 10000 bps")]
+    InvalidAllocationWeight,
+
+    // 分配的金库数量超过上限错误
+    #[msg("Too many allocations")]
+    TooManyAllocations,
+
+    // 持仓超过金库上限错误
+    #[msg("Allocation cap exceeded")]
+    AllocationCapExceeded,
+
+    // 金库已达到目标权重，无需再平衡
+    #[msg("Nothing to rebalance")]
+    NothingToRebalance,
+
+    // 金库已登记存款人错误
+    #[msg("Vault already registered")]
+    VaultAlreadyRegistered,
+
+    // 金库未登记存款人或存款人账户不匹配错误
+    #[msg("Vault not registered or vault depositor mismatch")]
+    VaultNotRegistered,
+
+    // 金库的现货市场与代币不匹配错误
+    #[msg("Vault spot market does not match the treasury mint")]
+    InvalidSpotMarket,
+
+    // 传入的drift账户与金库不匹配错误
+    #[msg("Drift account does not match the vault")]
+    InvalidDriftAccount,
+
+    // 消息接收方不是本程序错误
+    #[msg("Invalid message recipient")]
+    InvalidMessageRecipient,
+
+    // 销毁消息体版本与代币消息传输器不符错误
+    #[msg("Invalid message body version")]
+    InvalidMessageBodyVersion,
+
+    // 代币精度与金库要求的精度不符错误
+    #[msg("Invalid mint decimals")]
+    InvalidMintDecimals,
 }
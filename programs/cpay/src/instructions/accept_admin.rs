@@ -0,0 +1,34 @@
+use anchor_lang::context::Context;
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::*;
+
+// 接受管理员转移
+pub(crate) fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let access_control = &mut ctx.accounts.access_control;
+    let previous_admin = access_control.admin;
+    access_control.admin = access_control.pending_admin;
+    access_control.pending_admin = Pubkey::default();
+
+    emit!(AdminTransferred {
+        previous_admin,
+        new_admin: access_control.admin,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    /// 权限控制账户
+    #[account(
+        mut,
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        has_one = pending_admin @ ErrorCode::InvalidPendingAuthority,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    /// 待接受的新管理员
+    pub pending_admin: Signer<'info>,
+}
@@ -0,0 +1,201 @@
+use anchor_lang::context::Context;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::instructions;
+use crate::instructions::constraints::*;
+use crate::state::*;
+use drift_vaults::program::DriftVaults;
+use drift_vaults::state::{Vault, VaultDepositor};
+use drift::program::Drift;
+use drift::state::user::User;
+use crate::error::ErrorCode;
+
+// 赎回期结束后从drift金库取回资金，按实际到账数量计入闲置资金
+pub(crate) fn complete_unstake<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CompleteUnstake<'info>>,
+) -> Result<()> {
+    ctx.accounts.global_state.require_not_paused(instructions::GlobalState::PAUSE_UNSTAKE)?;
+
+    // 记录赎回请求价值和取回前的余额，drift金库可能因亏损少于请求价值支付
+    let value = ctx.accounts.vault_depositor.load()?.last_withdraw_request.value;
+    let balance_before = ctx.accounts.program_token_account.amount;
+
+    // 构造withdraw指令所需的账户
+    let cpi_accounts = drift_vaults::cpi::accounts::Withdraw {
+        vault: ctx.accounts.vault.to_account_info(),
+        vault_depositor: ctx.accounts.vault_depositor.to_account_info(),
+        authority: ctx.accounts.global_state.to_account_info(),
+        vault_token_account: ctx.accounts.vault_token_account.to_account_info(),
+        drift_user_stats: ctx.accounts.drift_user_stats.to_account_info(),
+        drift_user: ctx.accounts.drift_user.to_account_info(),
+        drift_state: ctx.accounts.drift_state.to_account_info(),
+        drift_spot_market_vault: ctx.accounts.drift_spot_market_vault.to_account_info(),
+        user_token_account: ctx.accounts.program_token_account.to_account_info(),
+        drift_program: ctx.accounts.drift_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        drift_signer: ctx.accounts.drift_signer.to_account_info(),
+    };
+
+    // 调用drift_vaults的withdraw指令，剩余账户原样转发
+    let global_state_seeds = ctx.accounts.global_state.signer_seeds();
+    drift_vaults::cpi::withdraw(
+        CpiContext::new_with_signer(
+            ctx.accounts.drift_vaults_program.to_account_info(),
+            cpi_accounts,
+            &[&global_state_seeds],
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+    )?;
+
+    // 以代币账户的实际余额变化作为到账数量
+    ctx.accounts.program_token_account.reload()?;
+    let received = ctx.accounts.program_token_account.amount
+        .checked_sub(balance_before)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // 更新资金账户状态
+    let fund_account = &mut ctx.accounts.fund_account;
+    fund_account.complete_unstake(&mut ctx.accounts.position, value, received)?;
+    fund_account.last_update_time = Clock::get()?.unix_timestamp;
+    ctx.accounts.global_state.record_unstake_completed(value, received)?;
+
+    // 发出质押状态变更事件
+    emit!(StakeStatusChanged {
+        amount: received,
+        is_stake: false,
+        stake_account: ctx.accounts.vault_depositor.key(),
+        timestamp: fund_account.last_update_time,
+    });
+
+    // 发出资金状态变更事件
+    emit!(FundStatusChanged {
+        amount: received,
+        from_status: FundStatus::Unstaking, // 从赎回中状态
+        to_status: FundStatus::Idle,        // 变为闲置状态
+        token_account: ctx.accounts.program_token_account.key(),
+        timestamp: fund_account.last_update_time,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CompleteUnstake<'info> {
+    /// 全局状态账户，drift金库存款人的authority
+    #[account(mut, seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Box<Account<'info, instructions::GlobalState>>,
+
+    /// 权限控制账户，调用者必须拥有操作员角色
+    #[account(
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        constraint = access_control.has_role(Role::Operator, &payer.key()) @ ErrorCode::UnauthorizedRole,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    /// 资金账户，用于跟踪资金状态（闲置、质押等）
+    #[account(
+        mut,
+        seeds = [b"fund_account", program_token_account.key().as_ref()],
+        bump,
+    )]
+    pub fund_account: Account<'info, FundAccount>,
+
+    /// 资金账户在该金库的持仓，存款人账户必须已通过 register_vault 登记
+    #[account(
+        mut,
+        seeds = [b"position", fund_account.key().as_ref(), vault.key().as_ref()],
+        bump = position.bump,
+        has_one = vault_depositor @ ErrorCode::VaultNotRegistered,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// USDC代币铸造账户
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// Drift金库账户，用于管理质押资金
+    #[account(mut)]
+    pub vault: AccountLoader<'info, Vault>,
+
+    /// 金库的代币账户，用于转出质押的代币
+    #[account(
+        mut,
+        seeds = [b"vault_token_account", vault.key().as_ref()],
+        bump,
+        seeds::program = drift_vaults_program.key(),
+        token::mint = mint,
+    )]
+    pub vault_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// 金库存款人账户，必须已登记且由全局状态持有
+    #[account(
+        mut,
+        seeds = [b"vault_depositor", vault.key().as_ref(), global_state.key().as_ref()],
+        bump,
+        seeds::program = drift_vaults_program.key(),
+        constraint = is_vault_for_vault_depositor(&vault_depositor, &vault)? @ ErrorCode::InvalidDriftAccount,
+        constraint = is_authority_for_vault_depositor(&vault_depositor, &global_state.key())? @ ErrorCode::InvalidDriftAccount,
+    )]
+    pub vault_depositor: AccountLoader<'info, VaultDepositor>,
+
+    /// 程序的代币账户，用于接收取回的代币
+    #[account(
+        mut,
+        seeds = [b"program_token_account", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = global_state,
+    )]
+    pub program_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Drift用户统计账户，必须属于该金库 CHECK: drift program
+    #[account(
+        mut,
+        constraint = is_user_stats_for_vault(&vault, &drift_user_stats)? @ ErrorCode::InvalidDriftAccount,
+    )]
+    pub drift_user_stats: AccountInfo<'info>,
+
+    /// Drift用户账户，必须是金库在drift中的持仓账户
+    #[account(
+        mut,
+        constraint = is_user_for_vault(&vault, &drift_user)? @ ErrorCode::InvalidDriftAccount,
+    )]
+    pub drift_user: AccountLoader<'info, User>,
+
+    /// Drift状态账户 CHECK: drift program
+    #[account(
+        mut,
+        seeds = [b"drift_state"],
+        bump,
+        seeds::program = drift_program.key(),
+    )]
+    pub drift_state: AccountInfo<'info>,
+
+    /// Drift现货市场金库账户，必须与金库代币相同
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub drift_spot_market_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Drift签名者账户 CHECK: drift program
+    #[account(
+        seeds = [b"drift_signer"],
+        bump,
+        seeds::program = drift_program.key(),
+    )]
+    pub drift_signer: AccountInfo<'info>,
+
+    /// Drift程序ID
+    pub drift_program: Program<'info, Drift>,
+
+    /// Drift金库程序ID
+    pub drift_vaults_program: Program<'info, DriftVaults>,
+
+    /// Token程序ID
+    pub token_program: Program<'info, Token>,
+
+    /// 交易发起人，必须是操作员
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
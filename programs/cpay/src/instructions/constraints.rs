@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use drift::state::user::User;
+use drift_vaults::state::{Vault, VaultDepositor};
+
+// 与 drift_vaults 的 instructions::constraints 保持一致，在CPI之前校验传入的drift账户
+
+// 存款人账户属于该金库
+pub fn is_vault_for_vault_depositor(
+    vault_depositor: &AccountLoader<VaultDepositor>,
+    vault: &AccountLoader<Vault>,
+) -> Result<bool> {
+    Ok(vault_depositor.load()?.vault.eq(&vault.key()))
+}
+
+// 存款人账户由指定authority持有
+pub fn is_authority_for_vault_depositor(
+    vault_depositor: &AccountLoader<VaultDepositor>,
+    authority: &Pubkey,
+) -> Result<bool> {
+    Ok(vault_depositor.load()?.authority.eq(authority))
+}
+
+// Drift用户账户是金库在drift中的持仓账户
+pub fn is_user_for_vault(vault: &AccountLoader<Vault>, user: &AccountLoader<User>) -> Result<bool> {
+    Ok(vault.load()?.user.eq(&user.key()))
+}
+
+// Drift用户统计账户属于该金库
+pub fn is_user_stats_for_vault(vault: &AccountLoader<Vault>, user_stats: &AccountInfo) -> Result<bool> {
+    Ok(vault.load()?.user_stats.eq(user_stats.key))
+}
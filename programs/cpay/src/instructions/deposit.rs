@@ -2,21 +2,71 @@ use anchor_lang::context::Context;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use crate::instructions;
+use crate::instructions::constraints::*;
 use crate::state::*;
+use drift_vaults::program::DriftVaults;
 use drift_vaults::state::{Vault, VaultDepositor};
 use drift::program::Drift;
 use drift::state::user::User;
 use crate::error::ErrorCode;
 
-pub(crate) fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
-    require!(ctx.accounts.global_state.admin.key().eq(ctx.accounts.payer.key), ErrorCode::UnauthorizedAdmin);
-
+// 质押闲置资金到drift金库，剩余账户需要按 drift_vaults 的约定传入现货/永续市场和预言机账户
+pub(crate) fn deposit<'c: 'info, 'info>(
+    mut ctx: Context<'_, '_, 'c, 'info, Deposit<'info>>,
+    amount: u64,
+) -> Result<()> {
     // 验证闲置资金是否足够
     require!(
         ctx.accounts.fund_account.idle_amount >= amount,
         ErrorCode::InsufficientIdleFunds
     );
 
+    deposit_internal(&mut ctx, amount)
+}
+
+// 把闲置资金质押到低于目标权重的金库，数量为与目标持仓价值的差额，不超过闲置资金
+pub(crate) fn rebalance<'c: 'info, 'info>(
+    mut ctx: Context<'_, '_, 'c, 'info, Deposit<'info>>,
+) -> Result<()> {
+    let vault = ctx.accounts.vault.key();
+    let total_value = ctx.accounts.fund_account.total_value()?;
+    let allocation_config = &ctx.accounts.allocation_config;
+    let target_value = allocation_config.target_value(&vault, total_value)?;
+    let amount = allocation_config.rebalance_amount(
+        &vault,
+        total_value,
+        ctx.accounts.position.staked_value,
+        ctx.accounts.fund_account.idle_amount,
+    )?;
+    require!(amount > 0, ErrorCode::NothingToRebalance);
+
+    deposit_internal(&mut ctx, amount)?;
+
+    // 发出再平衡事件
+    emit!(Rebalanced {
+        fund_account: ctx.accounts.fund_account.key(),
+        vault,
+        amount,
+        target_value,
+        staked_value: ctx.accounts.position.staked_value,
+        timestamp: ctx.accounts.fund_account.last_update_time,
+    });
+
+    Ok(())
+}
+
+fn deposit_internal<'c: 'info, 'info>(
+    ctx: &mut Context<'_, '_, 'c, 'info, Deposit<'info>>,
+    amount: u64,
+) -> Result<()> {
+    ctx.accounts.global_state.require_not_paused(instructions::GlobalState::PAUSE_STAKE)?;
+
+    // 验证质押后的持仓价值不超过金库上限
+    let staked_value = ctx.accounts.position.staked_value
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    ctx.accounts.allocation_config.check_cap(&ctx.accounts.vault.key(), staked_value)?;
+
     // 构造deposit指令所需的账户
     let deposit_accounts = drift_vaults::cpi::accounts::Deposit {
         vault: ctx.accounts.vault.to_account_info(),
@@ -30,29 +80,27 @@ pub(crate) fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
         user_token_account: ctx.accounts.program_token_account.to_account_info(),
         drift_program: ctx.accounts.drift_program.to_account_info(),
         token_program: ctx.accounts.token_program.to_account_info(),
+        // 仅许可制金库需要白名单条目
+        allowlist_entry: None,
     };
 
-    // 调用drift_vaults的deposit指令
-    drift_vaults::cpi::deposit(CpiContext::new_with_signer(
-        ctx.accounts.drift_program.to_account_info(),
-        deposit_accounts,
-        &[
-            &[
-                "program_token_account".as_bytes(),
-                &[ctx.bumps.global_state]
-            ],
-        ]
-        ),
+    // 调用drift_vaults的deposit指令，剩余账户（现货/永续市场、预言机）原样转发
+    let global_state_seeds = ctx.accounts.global_state.signer_seeds();
+    drift_vaults::cpi::deposit(
+        CpiContext::new_with_signer(
+            ctx.accounts.drift_vaults_program.to_account_info(),
+            deposit_accounts,
+            &[&global_state_seeds],
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
         amount,
     )?;
 
     // 更新资金账户状态
     let fund_account = &mut ctx.accounts.fund_account;
-    fund_account.idle_amount = fund_account.idle_amount.checked_sub(amount)
-        .ok_or(ErrorCode::MathOverflow)?;
-    fund_account.staked_amount = fund_account.staked_amount.checked_add(amount)
-        .ok_or(ErrorCode::MathOverflow)?;
+    fund_account.stake(&mut ctx.accounts.position, amount)?;
     fund_account.last_update_time = Clock::get()?.unix_timestamp;
+    ctx.accounts.global_state.record_staked(amount)?;
 
     // 发出质押状态变更事件
     emit!(StakeStatusChanged {
@@ -77,9 +125,17 @@ pub(crate) fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
 #[derive(Accounts)]
 pub struct Deposit<'info> {
     /// 全局状态账户，存储管理员地址等信息
-    #[account(mut,seeds = ["global_state".as_bytes()],bump)]
+    #[account(mut, seeds = [b"global_state"], bump = global_state.bump)]
     pub global_state: Box<Account<'info, instructions::GlobalState>>,
 
+    /// 权限控制账户，调用者必须拥有操作员角色
+    #[account(
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        constraint = access_control.has_role(Role::Operator, &payer.key()) @ ErrorCode::UnauthorizedRole,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
     /// 资金账户，用于跟踪资金状态（闲置、质押等）
     #[account(
         mut,
@@ -88,6 +144,22 @@ pub struct Deposit<'info> {
     )]
     pub fund_account: Account<'info, FundAccount>,
 
+    /// 资金账户的分配配置，金库必须已配置分配目标
+    #[account(
+        seeds = [b"allocation_config", fund_account.key().as_ref()],
+        bump = allocation_config.bump,
+    )]
+    pub allocation_config: Box<Account<'info, AllocationConfig>>,
+
+    /// 资金账户在该金库的持仓，存款人账户必须已通过 register_vault 登记
+    #[account(
+        mut,
+        seeds = [b"position", fund_account.key().as_ref(), vault.key().as_ref()],
+        bump = position.bump,
+        has_one = vault_depositor @ ErrorCode::VaultNotRegistered,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
     /// USDC代币铸造账户
     pub mint: Box<Account<'info, Mint>>,
 
@@ -98,41 +170,74 @@ pub struct Deposit<'info> {
     /// 程序的代币账户，用于存储用户存入的代币
     #[account(
         mut,
+        seeds = [b"program_token_account", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
         token::authority = global_state,
     )]
     pub program_token_account: Box<Account<'info, TokenAccount>>,
 
     /// 金库的代币账户，用于接收质押的代币
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"vault_token_account", vault.key().as_ref()],
+        bump,
+        seeds::program = drift_vaults_program.key(),
+        token::mint = mint,
+    )]
     pub vault_token_account: Box<Account<'info, TokenAccount>>,
 
-    /// 金库存款人账户，记录质押份额等信息
-    #[account(mut)]
+    /// 金库存款人账户，必须已登记且由全局状态持有
+    #[account(
+        mut,
+        seeds = [b"vault_depositor", vault.key().as_ref(), global_state.key().as_ref()],
+        bump,
+        seeds::program = drift_vaults_program.key(),
+        constraint = is_vault_for_vault_depositor(&vault_depositor, &vault)? @ ErrorCode::InvalidDriftAccount,
+        constraint = is_authority_for_vault_depositor(&vault_depositor, &global_state.key())? @ ErrorCode::InvalidDriftAccount,
+    )]
     pub vault_depositor: AccountLoader<'info, VaultDepositor>,
 
-    /// Drift用户统计账户  CHECK: drift program
-    #[account(mut)]
+    /// Drift用户统计账户，必须属于该金库 CHECK: drift program
+    #[account(
+        mut,
+        constraint = is_user_stats_for_vault(&vault, &drift_user_stats)? @ ErrorCode::InvalidDriftAccount,
+    )]
     pub drift_user_stats: AccountInfo<'info>,
 
-    /// Drift用户账户 CHECK: drift program
-    #[account(mut)]
+    /// Drift用户账户，必须是金库在drift中的持仓账户
+    #[account(
+        mut,
+        constraint = is_user_for_vault(&vault, &drift_user)? @ ErrorCode::InvalidDriftAccount,
+    )]
     pub drift_user: AccountLoader<'info, User>,
 
     /// Drift状态账户 CHECK: drift program
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"drift_state"],
+        bump,
+        seeds::program = drift_program.key(),
+    )]
     pub drift_state: AccountInfo<'info>,
 
-    /// Drift现货市场金库账户 CHECK: drift program
-    #[account(mut)]
-    pub drift_spot_market_vault: AccountInfo<'info>,
+    /// Drift现货市场金库账户，必须与金库代币相同
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub drift_spot_market_vault: Box<Account<'info, TokenAccount>>,
 
     /// Drift程序ID
     pub drift_program: Program<'info, Drift>,
 
+    /// Drift金库程序ID
+    pub drift_vaults_program: Program<'info, DriftVaults>,
+
     /// Token程序ID
     pub token_program: Program<'info, Token>,
 
-    /// 交易发起人，必须是管理员
+    /// 交易发起人，必须是操作员
     #[account(mut)]
     pub payer: Signer<'info>,
 }
@@ -1,19 +1,26 @@
 use anchor_lang::context::Context;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
-use std::mem::size_of;
 use crate::error::ErrorCode;
+use crate::state::AccessControl;
 
-// 初始化指令的处理函数
+// 初始化指令的处理函数，创建全局状态和权限控制账户，金库由 initialize_fund_account 按代币逐个创建
 pub(crate) fn initialize(ctx: Context<Initialize>) -> Result<()> {
     let global_state = &mut ctx.accounts.global_state;
     require!(!global_state.initialized, ErrorCode::RepeatedInit);
     global_state.initialized = true;
-    global_state.admin = ctx.accounts.payer.key();
-    global_state.token_account = ctx.accounts.program_token_account.key();
+    global_state.bump = ctx.bumps.global_state;
+    global_state.paused_operations = 0;
     global_state.total_funds = 0;
     global_state.staked_funds = 0;
+    global_state.unstaking_funds = 0;
     global_state.idle_funds = 0;
+    global_state.treasuries = Vec::new();
+
+    // 初始化者成为权限控制管理员，其余角色由管理员单独授予
+    let access_control = &mut ctx.accounts.access_control;
+    access_control.admin = ctx.accounts.payer.key();
+    access_control.pending_admin = Pubkey::default();
+    access_control.bump = ctx.bumps.access_control;
     Ok(())
 }
 
@@ -23,50 +30,194 @@ pub struct Initialize<'info> {
     #[account(
     init,
     seeds = ["global_state".as_bytes()],
-    bump, payer = payer, space = size_of::<GlobalState>()+8)
+    bump, payer = payer, space = 8 + GlobalState::LEN)
     ]
     pub global_state: Box<Account<'info, GlobalState>>,
 
+    // 权限控制账户
     #[account(
         init,
-        seeds = [b"program_token_account".as_ref(), global_state.key().as_ref()],
+        seeds = [b"access_control".as_ref()],
         bump,
         payer = payer,
-        token::mint = mint,
-        token::authority = global_state
+        space = AccessControl::LEN
     )]
-    pub program_token_account: Box<Account<'info, TokenAccount>>,
-
-    pub mint: Box<Account<'info, Mint>>,
+    pub access_control: Box<Account<'info, AccessControl>>,
 
     #[account(mut)]
     pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
 
 }
 
 
+// 程序管理的单个金库，每种代币一个
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Treasury {
+    // 代币铸造账户
+    pub mint: Pubkey,
+    // 金库代币账户，按 [program_token_account, mint] 派生
+    pub token_account: Pubkey,
+    // 资金账户，按 [fund_account, token_account] 派生
+    pub fund_account: Pubkey,
+}
+
+impl Treasury {
+    pub const LEN: usize = 32 + 32 + 32;
+}
+
+
 #[account]
 pub struct GlobalState {
     pub initialized: bool,
-    pub admin: Pubkey,
-    pub token_account: Pubkey,
+    pub bump: u8,
+    // 已暂停的操作，按位组合 PAUSE_* 常量
+    pub paused_operations: u8,
 
+    // 以下各项为所有金库 FundAccount 的汇总，按代币最小单位直接相加（各金库均为6位精度的稳定币）
     pub total_funds: u64,
 
     pub staked_funds: u64,
 
+    pub unstaking_funds: u64,
+
     pub idle_funds: u64,
 
+    // 已创建的金库列表
+    pub treasuries: Vec<Treasury>,
+
 }
 
 impl GlobalState {
+    // 最多管理的金库数量
+    pub const MAX_TREASURIES: usize = 8;
+
+    // 金库代币的精度，汇总金额按最小单位直接相加，所有金库必须使用相同精度
+    pub const USDC_DECIMALS: u8 = 6;
+
+    // 可单独暂停的操作
+    pub const PAUSE_STAKE: u8 = 1 << 0;
+    pub const PAUSE_UNSTAKE: u8 = 1 << 1;
+    pub const PAUSE_BURN: u8 = 1 << 2;
+    pub const PAUSE_RECEIVE: u8 = 1 << 3;
+    pub const PAUSE_ALL: u8 =
+        Self::PAUSE_STAKE | Self::PAUSE_UNSTAKE | Self::PAUSE_BURN | Self::PAUSE_RECEIVE;
+
     // 账户大小常量
-    pub const LEN: usize = 32 + // admin pubkey
-        32 + // token_account pubkey
+    pub const LEN: usize = 1 + // initialized
+        1 +  // bump
+        1 +  // paused_operations
         8 +  // total_funds
         8 +  // staked_funds
+        8 +  // unstaking_funds
         8 +  // idle_funds
-        1;   // bump
+        4 + Self::MAX_TREASURIES * Treasury::LEN; // treasuries
+
+    // global_state 作为金库代币账户和drift存款人的authority时使用的签名种子
+    pub fn signer_seeds(&self) -> [&[u8]; 2] {
+        [b"global_state".as_ref(), std::slice::from_ref(&self.bump)]
+    }
+
+    // 判断操作是否已暂停
+    pub fn is_paused(&self, operation: u8) -> bool {
+        self.paused_operations & operation != 0
+    }
+
+    // 检查操作未暂停
+    pub fn require_not_paused(&self, operation: u8) -> Result<()> {
+        require!(!self.is_paused(operation), ErrorCode::OperationPaused);
+        Ok(())
+    }
+
+    // 暂停或恢复一组操作
+    pub fn set_paused(&mut self, operations: u8, paused: bool) -> Result<()> {
+        require!(
+            operations != 0 && operations & !Self::PAUSE_ALL == 0,
+            ErrorCode::InvalidArgument
+        );
+
+        if paused {
+            self.paused_operations |= operations;
+        } else {
+            self.paused_operations &= !operations;
+        }
+        Ok(())
+    }
+
+    // 查找代币对应的金库
+    pub fn treasury(&self, mint: &Pubkey) -> Option<&Treasury> {
+        self.treasuries.iter().find(|treasury| &treasury.mint == mint)
+    }
+
+    // 登记新金库
+    pub fn add_treasury(&mut self, treasury: Treasury) -> Result<()> {
+        require!(
+            self.treasury(&treasury.mint).is_none(),
+            ErrorCode::TreasuryAlreadyExists
+        );
+        require!(
+            self.treasuries.len() < Self::MAX_TREASURIES,
+            ErrorCode::TooManyTreasuries
+        );
+
+        self.treasuries.push(treasury);
+        Ok(())
+    }
+
+    // 跨链转入资金，计入闲置资金
+    pub fn record_received(&mut self, amount: u64) -> Result<()> {
+        self.idle_funds = self.idle_funds.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        self.total_funds = self.total_funds.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    // 跨链转出资金，从闲置资金中扣除
+    pub fn record_sent(&mut self, amount: u64) -> Result<()> {
+        self.idle_funds = self.idle_funds.checked_sub(amount).ok_or(ErrorCode::MathOverflow)?;
+        self.total_funds = self.total_funds.checked_sub(amount).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    // 闲置资金转为质押
+    pub fn record_staked(&mut self, amount: u64) -> Result<()> {
+        self.idle_funds = self.idle_funds.checked_sub(amount).ok_or(ErrorCode::MathOverflow)?;
+        self.staked_funds = self.staked_funds.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    // 发起赎回，value 为赎回请求的价值，principal 为其对应的质押本金，两者之差为已实现盈亏
+    pub fn record_unstake_requested(&mut self, value: u64, principal: u64) -> Result<()> {
+        self.staked_funds = self.staked_funds.checked_sub(principal).ok_or(ErrorCode::MathOverflow)?;
+        self.unstaking_funds = self.unstaking_funds.checked_add(value).ok_or(ErrorCode::MathOverflow)?;
+        self.total_funds = self
+            .total_funds
+            .checked_add(value)
+            .and_then(|total| total.checked_sub(principal))
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    // 取消赎回，赎回中的资金按请求价值移出，质押恢复为对应的本金，并冲回发起赎回时计入总额的差额
+    pub fn record_unstake_cancelled(&mut self, value: u64, principal: u64) -> Result<()> {
+        self.unstaking_funds = self.unstaking_funds.checked_sub(value).ok_or(ErrorCode::MathOverflow)?;
+        self.staked_funds = self.staked_funds.checked_add(principal).ok_or(ErrorCode::MathOverflow)?;
+        self.total_funds = self
+            .total_funds
+            .checked_add(principal)
+            .and_then(|total| total.checked_sub(value))
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    // 完成赎回，按实际到账数量计入闲置资金，与请求价值的差额计入总额
+    pub fn record_unstake_completed(&mut self, value: u64, received: u64) -> Result<()> {
+        self.unstaking_funds = self.unstaking_funds.checked_sub(value).ok_or(ErrorCode::MathOverflow)?;
+        self.idle_funds = self.idle_funds.checked_add(received).ok_or(ErrorCode::MathOverflow)?;
+        self.total_funds = self
+            .total_funds
+            .checked_add(received)
+            .and_then(|total| total.checked_sub(value))
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
 }
\ No newline at end of file
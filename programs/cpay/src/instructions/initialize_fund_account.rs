@@ -0,0 +1,87 @@
+use anchor_lang::context::Context;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::error::ErrorCode;
+use crate::instructions::{GlobalState, Treasury};
+use crate::state::{AccessControl, AllocationConfig, FundAccount};
+
+// 为指定代币创建金库代币账户和资金账户，并登记到全局状态
+pub(crate) fn initialize_fund_account(ctx: Context<InitializeFundAccount>) -> Result<()> {
+    let fund_account = &mut ctx.accounts.fund_account;
+    fund_account.authority = ctx.accounts.payer.key();
+    fund_account.last_update_time = Clock::get()?.unix_timestamp;
+
+    let allocation_config = &mut ctx.accounts.allocation_config;
+    allocation_config.fund_account = ctx.accounts.fund_account.key();
+    allocation_config.bump = ctx.bumps.allocation_config;
+
+    ctx.accounts.global_state.add_treasury(Treasury {
+        mint: ctx.accounts.mint.key(),
+        token_account: ctx.accounts.program_token_account.key(),
+        fund_account: ctx.accounts.fund_account.key(),
+    })?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeFundAccount<'info> {
+    /// 全局状态账户，金库代币账户的authority
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// 权限控制账户，调用者必须是管理员
+    #[account(
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        constraint = access_control.admin == payer.key() @ ErrorCode::UnauthorizedAdmin,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    /// 金库代币账户，所有指令统一按 [program_token_account, mint] 派生
+    #[account(
+        init,
+        seeds = [b"program_token_account".as_ref(), mint.key().as_ref()],
+        bump,
+        payer = payer,
+        token::mint = mint,
+        token::authority = global_state
+    )]
+    pub program_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// 金库对应的资金账户
+    #[account(
+        init,
+        seeds = [b"fund_account".as_ref(), program_token_account.key().as_ref()],
+        bump,
+        payer = payer,
+        space = FundAccount::LEN
+    )]
+    pub fund_account: Box<Account<'info, FundAccount>>,
+
+    /// 资金账户在各drift金库之间的分配配置
+    #[account(
+        init,
+        seeds = [b"allocation_config".as_ref(), fund_account.key().as_ref()],
+        bump,
+        payer = payer,
+        space = AllocationConfig::LEN
+    )]
+    pub allocation_config: Box<Account<'info, AllocationConfig>>,
+
+    /// 金库代币的铸造账户，精度必须与全局汇总金额一致
+    #[account(
+        constraint = mint.decimals == GlobalState::USDC_DECIMALS @ ErrorCode::InvalidMintDecimals,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// 交易发起人，必须是管理员
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
@@ -2,10 +2,52 @@ mod initialize;
 
 pub use initialize::*;
 
+mod initialize_fund_account;
+
+pub use initialize_fund_account::*;
+
 mod deposit;
 
 pub use deposit::*;
 
-mod withdraw;
+mod request_unstake;
+
+pub use request_unstake::*;
+
+mod cancel_unstake;
+
+pub use cancel_unstake::*;
+
+mod complete_unstake;
+
+pub use complete_unstake::*;
+
+mod update_role;
+
+pub use update_role::*;
+
+mod transfer_admin;
+
+pub use transfer_admin::*;
+
+mod accept_admin;
+
+pub use accept_admin::*;
+
+mod pause;
+
+pub use pause::*;
+
+mod sync_staked_value;
+
+pub use sync_staked_value::*;
+
+mod set_allocation;
+
+pub use set_allocation::*;
+
+mod register_vault;
+
+pub use register_vault::*;
 
-pub use withdraw::*;
\ No newline at end of file
+pub(crate) mod constraints;
\ No newline at end of file
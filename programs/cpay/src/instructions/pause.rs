@@ -0,0 +1,52 @@
+use anchor_lang::context::Context;
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::instructions::GlobalState;
+use crate::state::*;
+
+// 暂停一组操作，传入 GlobalState::PAUSE_ALL 即全部暂停
+pub(crate) fn pause(ctx: Context<Pause>, operations: u8) -> Result<()> {
+    set_paused(ctx, operations, true)
+}
+
+// 恢复一组操作
+pub(crate) fn unpause(ctx: Context<Pause>, operations: u8) -> Result<()> {
+    set_paused(ctx, operations, false)
+}
+
+fn set_paused(ctx: Context<Pause>, operations: u8, paused: bool) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let previous_paused_operations = global_state.paused_operations;
+    global_state.set_paused(operations, paused)?;
+
+    emit!(PauseStateChanged {
+        previous_paused_operations,
+        paused_operations: global_state.paused_operations,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    /// 全局状态账户，保存暂停标志
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// 权限控制账户，调用者必须拥有暂停者角色
+    #[account(
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        constraint = access_control.has_role(Role::Pauser, &authority.key()) @ ErrorCode::UnauthorizedRole,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    /// 暂停者
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,121 @@
+use anchor_lang::context::Context;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use crate::error::ErrorCode;
+use crate::instructions;
+use crate::state::*;
+use drift::state::spot_market::SpotMarket;
+use drift_vaults::program::DriftVaults;
+use drift_vaults::state::Vault;
+
+// 以全局状态为authority在drift金库中创建存款人账户，并登记到对应持仓
+pub(crate) fn register_vault(ctx: Context<RegisterVault>) -> Result<()> {
+    require!(
+        ctx.accounts.position.vault_depositor == Pubkey::default(),
+        ErrorCode::VaultAlreadyRegistered
+    );
+
+    // 构造initialize_vault_depositor指令所需的账户，由管理员支付账户租金
+    let cpi_accounts = drift_vaults::cpi::accounts::InitializeVaultDepositor {
+        vault: ctx.accounts.vault.to_account_info(),
+        vault_depositor: ctx.accounts.vault_depositor.to_account_info(),
+        authority: ctx.accounts.global_state.to_account_info(),
+        payer: ctx.accounts.payer.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        // 仅许可制金库需要白名单条目
+        allowlist_entry: None,
+    };
+
+    // 调用drift_vaults的initialize_vault_depositor指令，全局状态PDA签名表示同意作为authority
+    let global_state_seeds = ctx.accounts.global_state.signer_seeds();
+    drift_vaults::cpi::initialize_vault_depositor(CpiContext::new_with_signer(
+        ctx.accounts.drift_vaults_program.to_account_info(),
+        cpi_accounts,
+        &[&global_state_seeds],
+    ))?;
+
+    // 登记存款人账户，后续质押和赎回指令按此校验传入的账户
+    let position = &mut ctx.accounts.position;
+    position.vault_depositor = ctx.accounts.vault_depositor.key();
+
+    emit!(VaultRegistered {
+        fund_account: ctx.accounts.fund_account.key(),
+        vault: ctx.accounts.vault.key(),
+        vault_depositor: position.vault_depositor,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterVault<'info> {
+    /// 全局状态账户，drift金库存款人的authority
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Box<Account<'info, instructions::GlobalState>>,
+
+    /// 权限控制账户，调用者必须是管理员
+    #[account(
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        constraint = access_control.admin == payer.key() @ ErrorCode::UnauthorizedAdmin,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    /// 资金账户
+    #[account(
+        seeds = [b"fund_account", program_token_account.key().as_ref()],
+        bump,
+    )]
+    pub fund_account: Box<Account<'info, FundAccount>>,
+
+    /// 资金账户在该金库的持仓，需先通过 set_allocation 创建
+    #[account(
+        mut,
+        seeds = [b"position", fund_account.key().as_ref(), vault.key().as_ref()],
+        bump = position.bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// 金库代币的铸造账户
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// 程序的代币账户
+    #[account(
+        seeds = [b"program_token_account", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = global_state,
+    )]
+    pub program_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Drift金库账户，必须使用与金库代币相同的现货市场
+    #[account(
+        constraint = vault.load()?.spot_market_index == drift_spot_market.load()?.market_index @ ErrorCode::InvalidSpotMarket,
+    )]
+    pub vault: AccountLoader<'info, Vault>,
+
+    /// 金库的Drift现货市场账户
+    #[account(
+        constraint = drift_spot_market.load()?.mint == mint.key() @ ErrorCode::InvalidSpotMarket,
+    )]
+    pub drift_spot_market: AccountLoader<'info, SpotMarket>,
+
+    /// 待创建的金库存款人账户 CHECK: drift_vaults program
+    #[account(
+        mut,
+        seeds = [b"vault_depositor", vault.key().as_ref(), global_state.key().as_ref()],
+        bump,
+        seeds::program = drift_vaults_program.key(),
+    )]
+    pub vault_depositor: AccountInfo<'info>,
+
+    /// Drift金库程序ID
+    pub drift_vaults_program: Program<'info, DriftVaults>,
+
+    /// 交易发起人，必须是管理员，支付存款人账户租金
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
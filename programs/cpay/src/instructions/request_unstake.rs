@@ -0,0 +1,145 @@
+use anchor_lang::context::Context;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use crate::instructions;
+use crate::instructions::constraints::*;
+use crate::state::*;
+use drift_vaults::program::DriftVaults;
+use drift_vaults::state::{Vault, VaultDepositor, VaultDepositorBase, WithdrawUnit};
+use drift::state::user::User;
+use crate::error::ErrorCode;
+
+// 向drift金库发起赎回请求，赎回期结束后通过 complete_unstake 取回资金
+pub(crate) fn request_unstake<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, RequestUnstake<'info>>,
+    amount: u64,
+    withdraw_unit: WithdrawUnit,
+) -> Result<()> {
+    ctx.accounts.global_state.require_not_paused(instructions::GlobalState::PAUSE_UNSTAKE)?;
+
+    // 构造request_withdraw指令所需的账户
+    let cpi_accounts = drift_vaults::cpi::accounts::RequestWithdraw {
+        vault: ctx.accounts.vault.to_account_info(),
+        vault_depositor: ctx.accounts.vault_depositor.to_account_info(),
+        authority: ctx.accounts.global_state.to_account_info(),
+        drift_user_stats: ctx.accounts.drift_user_stats.to_account_info(),
+        drift_user: ctx.accounts.drift_user.to_account_info(),
+    };
+
+    // 调用drift_vaults的request_withdraw指令，剩余账户（现货/永续市场、预言机）原样转发
+    let global_state_seeds = ctx.accounts.global_state.signer_seeds();
+    drift_vaults::cpi::request_withdraw(
+        CpiContext::new_with_signer(
+            ctx.accounts.drift_vaults_program.to_account_info(),
+            cpi_accounts,
+            &[&global_state_seeds],
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+        amount,
+        withdraw_unit,
+    )?;
+
+    // 以drift金库记录的请求价值作为赎回中的资金，按请求份额占比结转质押本金
+    let vault_depositor = ctx.accounts.vault_depositor.load()?;
+    let value = vault_depositor.last_withdraw_request.value;
+    let principal = ctx.accounts.position.principal_for_shares(
+        vault_depositor.last_withdraw_request.shares,
+        vault_depositor.get_vault_shares(),
+    )?;
+    drop(vault_depositor);
+
+    // 更新资金账户状态
+    let fund_account = &mut ctx.accounts.fund_account;
+    fund_account.request_unstake(&mut ctx.accounts.position, value, principal)?;
+    fund_account.last_update_time = Clock::get()?.unix_timestamp;
+    ctx.accounts.global_state.record_unstake_requested(value, principal)?;
+
+    // 发出资金状态变更事件
+    emit!(FundStatusChanged {
+        amount: value,
+        from_status: FundStatus::Staked,    // 从质押状态
+        to_status: FundStatus::Unstaking,   // 变为赎回中状态
+        token_account: ctx.accounts.program_token_account.key(),
+        timestamp: fund_account.last_update_time,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    /// 全局状态账户，drift金库存款人的authority
+    #[account(mut, seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Box<Account<'info, instructions::GlobalState>>,
+
+    /// 权限控制账户，调用者必须拥有操作员角色
+    #[account(
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        constraint = access_control.has_role(Role::Operator, &payer.key()) @ ErrorCode::UnauthorizedRole,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    /// 资金账户，用于跟踪资金状态（闲置、质押等）
+    #[account(
+        mut,
+        seeds = [b"fund_account", program_token_account.key().as_ref()],
+        bump,
+    )]
+    pub fund_account: Account<'info, FundAccount>,
+
+    /// 资金账户在该金库的持仓，存款人账户必须已通过 register_vault 登记
+    #[account(
+        mut,
+        seeds = [b"position", fund_account.key().as_ref(), vault.key().as_ref()],
+        bump = position.bump,
+        has_one = vault_depositor @ ErrorCode::VaultNotRegistered,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// USDC代币铸造账户
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// 程序的代币账户
+    #[account(
+        seeds = [b"program_token_account", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = global_state,
+    )]
+    pub program_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Drift金库账户
+    #[account(mut)]
+    pub vault: AccountLoader<'info, Vault>,
+
+    /// 金库存款人账户，记录赎回请求，必须已登记且由全局状态持有
+    #[account(
+        mut,
+        seeds = [b"vault_depositor", vault.key().as_ref(), global_state.key().as_ref()],
+        bump,
+        seeds::program = drift_vaults_program.key(),
+        constraint = is_vault_for_vault_depositor(&vault_depositor, &vault)? @ ErrorCode::InvalidDriftAccount,
+        constraint = is_authority_for_vault_depositor(&vault_depositor, &global_state.key())? @ ErrorCode::InvalidDriftAccount,
+    )]
+    pub vault_depositor: AccountLoader<'info, VaultDepositor>,
+
+    /// Drift用户统计账户，必须属于该金库 CHECK: drift program
+    #[account(
+        constraint = is_user_stats_for_vault(&vault, &drift_user_stats)? @ ErrorCode::InvalidDriftAccount,
+    )]
+    pub drift_user_stats: AccountInfo<'info>,
+
+    /// Drift用户账户，必须是金库在drift中的持仓账户
+    #[account(
+        constraint = is_user_for_vault(&vault, &drift_user)? @ ErrorCode::InvalidDriftAccount,
+    )]
+    pub drift_user: AccountLoader<'info, User>,
+
+    /// Drift金库程序ID
+    pub drift_vaults_program: Program<'info, DriftVaults>,
+
+    /// 交易发起人，必须是操作员
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
@@ -0,0 +1,70 @@
+use anchor_lang::context::Context;
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::*;
+use drift_vaults::state::Vault;
+
+// 设置资金账户在drift金库上的分配目标，首次设置时创建该金库的持仓账户
+pub(crate) fn set_allocation(ctx: Context<SetAllocation>, weight_bps: u16, cap: u64) -> Result<()> {
+    let vault = ctx.accounts.vault.key();
+    let fund_account = ctx.accounts.fund_account.key();
+
+    ctx.accounts.allocation_config.set_allocation(vault, weight_bps, cap)?;
+
+    let position = &mut ctx.accounts.position;
+    if position.vault == Pubkey::default() {
+        position.fund_account = fund_account;
+        position.vault = vault;
+        position.bump = ctx.bumps.position;
+    }
+
+    emit!(AllocationUpdated {
+        fund_account,
+        vault,
+        weight_bps,
+        cap,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetAllocation<'info> {
+    /// 权限控制账户，调用者必须是管理员
+    #[account(
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        constraint = access_control.admin == admin.key() @ ErrorCode::UnauthorizedAdmin,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    /// 资金账户
+    pub fund_account: Box<Account<'info, FundAccount>>,
+
+    /// 资金账户的分配配置
+    #[account(
+        mut,
+        seeds = [b"allocation_config", fund_account.key().as_ref()],
+        bump = allocation_config.bump,
+        has_one = fund_account @ ErrorCode::InvalidArgument,
+    )]
+    pub allocation_config: Box<Account<'info, AllocationConfig>>,
+
+    /// Drift金库账户
+    pub vault: AccountLoader<'info, Vault>,
+
+    /// 资金账户在该金库的持仓
+    #[account(
+        init_if_needed,
+        seeds = [b"position", fund_account.key().as_ref(), vault.key().as_ref()],
+        bump,
+        payer = admin,
+        space = Position::LEN
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// 管理员
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
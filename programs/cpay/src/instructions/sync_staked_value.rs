@@ -0,0 +1,123 @@
+use anchor_lang::context::Context;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use crate::instructions;
+use crate::instructions::constraints::*;
+use crate::state::*;
+use drift::instructions::optional_accounts::AccountMaps;
+use drift::math::insurance::if_shares_to_vault_amount as depositor_shares_to_vault_amount;
+use drift::state::user::User;
+use drift_vaults::state::{
+    AccountMapProvider, Vault, VaultDepositor, VaultDepositorBase, VaultProtocolProvider,
+};
+use crate::error::ErrorCode;
+
+// 按drift金库的当前权益重新计算该金库的持仓价值，任何人都可以调用
+// 剩余账户需要按 drift_vaults 的约定传入现货/永续市场和预言机账户
+pub(crate) fn sync_staked_value<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, SyncStakedValue<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault = ctx.accounts.vault.load()?;
+    let vault_depositor = ctx.accounts.vault_depositor.load()?;
+    let user = ctx.accounts.drift_user.load()?;
+
+    let AccountMaps {
+        perp_market_map,
+        spot_market_map,
+        mut oracle_map,
+    } = ctx.load_maps(clock.slot, None, ctx.vault_protocol().is_some())?;
+
+    let vault_equity =
+        vault.calculate_equity(&user, &perp_market_map, &spot_market_map, &mut oracle_map)?;
+
+    // 存款人份额可能尚未按金库的最新份额基数重新计算
+    let mut shares = vault_depositor.get_vault_shares();
+    let mut request_shares = vault_depositor.last_withdraw_request.shares;
+    let depositor_shares_base = vault_depositor.get_vault_shares_base();
+    if vault.shares_base > depositor_shares_base {
+        let rebase_divisor = 10_u128
+            .checked_pow(vault.shares_base - depositor_shares_base)
+            .ok_or(ErrorCode::MathOverflow)?;
+        shares /= rebase_divisor;
+        request_shares /= rebase_divisor;
+    }
+
+    // 赎回中的份额已按请求价值计入 unstaking_amount，这里只计算仍在质押的份额
+    // 价值为未扣除管理人分成的毛值
+    let staked_value = depositor_shares_to_vault_amount(
+        shares.saturating_sub(request_shares),
+        vault.total_shares,
+        vault_equity,
+    )?;
+
+    // 更新资金账户状态
+    let fund_account = &mut ctx.accounts.fund_account;
+    let unrealized_pnl_delta = fund_account.mark_staked_value(&mut ctx.accounts.position, staked_value)?;
+    fund_account.last_update_time = clock.unix_timestamp;
+
+    // 发出收益计提事件
+    emit!(YieldAccrued {
+        token_account: ctx.accounts.program_token_account.key(),
+        staked_amount: fund_account.staked_amount,
+        staked_value: fund_account.staked_value,
+        unrealized_pnl: fund_account.unrealized_pnl,
+        unrealized_pnl_delta,
+        realized_yield: fund_account.realized_yield,
+        timestamp: fund_account.last_update_time,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SyncStakedValue<'info> {
+    /// 全局状态账户，drift金库存款人的authority
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Box<Account<'info, instructions::GlobalState>>,
+
+    /// 资金账户，记录质押持仓价值和收益
+    #[account(
+        mut,
+        seeds = [b"fund_account", program_token_account.key().as_ref()],
+        bump,
+    )]
+    pub fund_account: Account<'info, FundAccount>,
+
+    /// 资金账户在该金库的持仓，存款人账户必须已通过 register_vault 登记
+    #[account(
+        mut,
+        seeds = [b"position", fund_account.key().as_ref(), vault.key().as_ref()],
+        bump = position.bump,
+        has_one = vault_depositor @ ErrorCode::VaultNotRegistered,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// USDC代币铸造账户
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// 程序的代币账户
+    #[account(
+        seeds = [b"program_token_account", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = global_state,
+    )]
+    pub program_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Drift金库账户
+    #[account(
+        constraint = is_user_for_vault(&vault, &drift_user)? @ ErrorCode::InvalidDriftAccount,
+    )]
+    pub vault: AccountLoader<'info, Vault>,
+
+    /// 金库存款人账户，必须属于该金库且由全局状态持有
+    #[account(
+        constraint = is_vault_for_vault_depositor(&vault_depositor, &vault)? @ ErrorCode::InvalidDriftAccount,
+        constraint = is_authority_for_vault_depositor(&vault_depositor, &global_state.key())? @ ErrorCode::InvalidDriftAccount,
+    )]
+    pub vault_depositor: AccountLoader<'info, VaultDepositor>,
+
+    /// Drift用户账户，金库在drift中的持仓
+    pub drift_user: AccountLoader<'info, User>,
+}
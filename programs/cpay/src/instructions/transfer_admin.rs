@@ -0,0 +1,34 @@
+use anchor_lang::context::Context;
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::*;
+
+// 发起管理员转移，新管理员需要调用 accept_admin 接受
+pub(crate) fn transfer_admin(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
+    require!(new_admin != Pubkey::default(), ErrorCode::InvalidArgument);
+
+    let access_control = &mut ctx.accounts.access_control;
+    access_control.pending_admin = new_admin;
+
+    emit!(AdminTransferStarted {
+        previous_admin: access_control.admin,
+        new_admin,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TransferAdmin<'info> {
+    /// 权限控制账户
+    #[account(
+        mut,
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    /// 当前管理员
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,45 @@
+use anchor_lang::context::Context;
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::*;
+
+// 授予角色
+pub(crate) fn grant_role(ctx: Context<UpdateRole>, role: Role, account: Pubkey) -> Result<()> {
+    ctx.accounts.access_control.grant_role(role, account)?;
+
+    emit!(RoleGranted {
+        role,
+        account,
+        admin: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+// 撤销角色
+pub(crate) fn revoke_role(ctx: Context<UpdateRole>, role: Role, account: Pubkey) -> Result<()> {
+    ctx.accounts.access_control.revoke_role(role, account)?;
+
+    emit!(RoleRevoked {
+        role,
+        account,
+        admin: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateRole<'info> {
+    /// 权限控制账户
+    #[account(
+        mut,
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    /// 管理员
+    pub admin: Signer<'info>,
+}
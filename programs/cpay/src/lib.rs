@@ -4,9 +4,12 @@ pub mod circle;
 pub mod error;
 pub mod instructions;
 pub mod state;
+mod tests;
 
 use circle::*;
 use instructions::*;
+use state::Role;
+use drift_vaults::state::WithdrawUnit;
 
 declare_id!("FNDpC3ADhBZSAhwHbh8ex9sGfGWQWBeDzWVcE1hhQehL");
 
@@ -18,12 +21,79 @@ pub mod cpay {
         instructions::initialize(ctx)
     }
 
-    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+    pub fn initialize_fund_account(ctx: Context<InitializeFundAccount>) -> Result<()> {
+        instructions::initialize_fund_account(ctx)
+    }
+
+    pub fn set_allocation(ctx: Context<SetAllocation>, weight_bps: u16, cap: u64) -> Result<()> {
+        instructions::set_allocation(ctx, weight_bps, cap)
+    }
+
+    pub fn register_vault(ctx: Context<RegisterVault>) -> Result<()> {
+        instructions::register_vault(ctx)
+    }
+
+    pub fn grant_role(ctx: Context<UpdateRole>, role: Role, account: Pubkey) -> Result<()> {
+        instructions::grant_role(ctx, role, account)
+    }
+
+    pub fn revoke_role(ctx: Context<UpdateRole>, role: Role, account: Pubkey) -> Result<()> {
+        instructions::revoke_role(ctx, role, account)
+    }
+
+    pub fn transfer_admin(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::transfer_admin(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin(ctx)
+    }
+
+    pub fn pause(ctx: Context<Pause>, operations: u8) -> Result<()> {
+        instructions::pause(ctx, operations)
+    }
+
+    pub fn unpause(ctx: Context<Pause>, operations: u8) -> Result<()> {
+        instructions::unpause(ctx, operations)
+    }
+
+    pub fn deposit<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, Deposit<'info>>,
+        amount: u64,
+    ) -> Result<()> {
         instructions::deposit(ctx, amount)
     }
 
-    pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
-        instructions::withdraw(ctx)
+    pub fn rebalance<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, Deposit<'info>>,
+    ) -> Result<()> {
+        instructions::rebalance(ctx)
+    }
+
+    pub fn request_unstake<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, RequestUnstake<'info>>,
+        amount: u64,
+        withdraw_unit: WithdrawUnit,
+    ) -> Result<()> {
+        instructions::request_unstake(ctx, amount, withdraw_unit)
+    }
+
+    pub fn cancel_unstake<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CancelUnstake<'info>>,
+    ) -> Result<()> {
+        instructions::cancel_unstake(ctx)
+    }
+
+    pub fn complete_unstake<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CompleteUnstake<'info>>,
+    ) -> Result<()> {
+        instructions::complete_unstake(ctx)
+    }
+
+    pub fn sync_staked_value<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SyncStakedValue<'info>>,
+    ) -> Result<()> {
+        instructions::sync_staked_value(ctx)
     }
 
     pub fn deposit_for_burn(
@@ -35,11 +105,132 @@ pub mod cpay {
         circle::deposit_for_burn(ctx, amount, destination_domain, mint_recipient)
     }
 
+    pub fn deposit_for_burn_with_caller(
+        ctx: Context<DepositForBurn>,
+        amount: u64,
+        destination_domain: u32,
+        mint_recipient: [u8; 32],
+        destination_caller: [u8; 32],
+    ) -> Result<()> {
+        circle::deposit_for_burn_with_caller(
+            ctx,
+            amount,
+            destination_domain,
+            mint_recipient,
+            destination_caller,
+        )
+    }
+
+    pub fn replace_deposit_for_burn(
+        ctx: Context<ReplaceDepositForBurn>,
+        new_mint_recipient: [u8; 32],
+        new_destination_caller: [u8; 32],
+    ) -> Result<()> {
+        circle::replace_deposit_for_burn(ctx, new_mint_recipient, new_destination_caller)
+    }
+
+    pub fn reclaim_burn_message(ctx: Context<ReclaimBurnMessage>) -> Result<()> {
+        circle::reclaim_burn_message(ctx)
+    }
+
+    pub fn set_burn_message_reclaim_delay(
+        ctx: Context<SetBurnMessageReclaimDelay>,
+        burn_message_reclaim_delay: i64,
+    ) -> Result<()> {
+        circle::set_burn_message_reclaim_delay(ctx, burn_message_reclaim_delay)
+    }
+
     pub fn receive_message(
         ctx: Context<ReceiveMessage>,
-        message: MessageData,
+        message: Vec<u8>,
         signatures: Vec<[u8; 65]>,
     ) -> Result<()> {
         circle::receive_message(ctx, message, signatures)
     }
+
+    pub fn initialize_message_transmitter(
+        ctx: Context<InitializeMessageTransmitter>,
+        params: InitializeMessageTransmitterParams,
+    ) -> Result<()> {
+        circle::initialize_message_transmitter(ctx, params)
+    }
+
+    pub fn initialize_token_messenger(
+        ctx: Context<InitializeTokenMessenger>,
+        params: InitializeTokenMessengerParams,
+    ) -> Result<()> {
+        circle::initialize_token_messenger(ctx, params)
+    }
+
+    pub fn pause_message_transmitter(ctx: Context<PauseMessageTransmitter>) -> Result<()> {
+        circle::pause_message_transmitter(ctx)
+    }
+
+    pub fn unpause_message_transmitter(ctx: Context<PauseMessageTransmitter>) -> Result<()> {
+        circle::unpause_message_transmitter(ctx)
+    }
+
+    pub fn transfer_ownership(ctx: Context<TransferOwnership>, new_authority: Pubkey) -> Result<()> {
+        circle::transfer_ownership(ctx, new_authority)
+    }
+
+    pub fn accept_ownership(ctx: Context<AcceptOwnership>) -> Result<()> {
+        circle::accept_ownership(ctx)
+    }
+
+    pub fn transfer_token_messenger_ownership(
+        ctx: Context<TransferTokenMessengerOwnership>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        circle::transfer_token_messenger_ownership(ctx, new_authority)
+    }
+
+    pub fn accept_token_messenger_ownership(
+        ctx: Context<AcceptTokenMessengerOwnership>,
+    ) -> Result<()> {
+        circle::accept_token_messenger_ownership(ctx)
+    }
+
+    pub fn add_remote_token_messenger(
+        ctx: Context<AddRemoteTokenMessenger>,
+        domain: u32,
+        token_messenger: [u8; 32],
+    ) -> Result<()> {
+        circle::add_remote_token_messenger(ctx, domain, token_messenger)
+    }
+
+    pub fn remove_remote_token_messenger(ctx: Context<RemoveRemoteTokenMessenger>) -> Result<()> {
+        circle::remove_remote_token_messenger(ctx)
+    }
+
+    pub fn link_token_pair(
+        ctx: Context<LinkTokenPair>,
+        remote_domain: u32,
+        remote_token: [u8; 32],
+    ) -> Result<()> {
+        circle::link_token_pair(ctx, remote_domain, remote_token)
+    }
+
+    pub fn unlink_token_pair(ctx: Context<UnlinkTokenPair>) -> Result<()> {
+        circle::unlink_token_pair(ctx)
+    }
+
+    pub fn set_burn_limits(ctx: Context<SetBurnLimits>, params: SetBurnLimitsParams) -> Result<()> {
+        circle::set_burn_limits(ctx, params)
+    }
+
+    pub fn enable_attester(ctx: Context<EnableAttester>, new_attester: Pubkey) -> Result<()> {
+        circle::enable_attester(ctx, new_attester)
+    }
+
+    pub fn disable_attester(ctx: Context<DisableAttester>, attester: Pubkey) -> Result<()> {
+        circle::disable_attester(ctx, attester)
+    }
+
+    pub fn set_signature_threshold(
+        ctx: Context<SetSignatureThreshold>,
+        new_signature_threshold: u32,
+    ) -> Result<()> {
+        circle::set_signature_threshold(ctx, new_signature_threshold)
+    }
 }
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
 
 // 资金状态枚举
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
@@ -9,6 +10,10 @@ pub enum FundStatus {
     Staked,
     // 已发送（跨链转出）
     Sent,
+    // 已接收（跨链转入）
+    Received,
+    // 赎回中（已向drift金库发起赎回请求，等待赎回期结束）
+    Unstaking,
 }
 
 // 资金状态变更事件
@@ -70,12 +75,20 @@ pub struct StakeStatusChanged {
 pub struct FundAccount {
     // 闲置资金数量
     pub idle_amount: u64,
-    // 质押中的资金数量
+    // 质押中的资金数量（所有持仓之和，下同）
     pub staked_amount: u64,
+    // 赎回中的资金数量（赎回请求的价值）
+    pub unstaking_amount: u64,
     // 已发送的资金数量（跨链转出）
     pub sent_amount: u64,
     // 总接收的资金数量（跨链转入）
     pub total_received_amount: u64,
+    // 最近一次按市值计算的质押持仓价值
+    pub staked_value: u64,
+    // 未实现盈亏（质押持仓价值 - 质押本金）
+    pub unrealized_pnl: i64,
+    // 累计已实现收益（赎回时确认，亏损为负）
+    pub realized_yield: i64,
     // 最后更新时间
     pub last_update_time: i64,
     // 管理员
@@ -86,8 +99,522 @@ impl FundAccount {
     pub const LEN: usize = 8 + // discriminator
         8 + // idle_amount
         8 + // staked_amount
+        8 + // unstaking_amount
         8 + // sent_amount
         8 + // total_received_amount
+        8 + // staked_value
+        8 + // unrealized_pnl
+        8 + // realized_yield
         8 + // last_update_time
         32; // authority
+
+    // 资金总价值（闲置 + 质押持仓价值 + 赎回中），作为分配目标的计算基数
+    pub fn total_value(&self) -> Result<u64> {
+        self.idle_amount
+            .checked_add(self.staked_value)
+            .and_then(|value| value.checked_add(self.unstaking_amount))
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    // 质押资金到指定持仓
+    pub fn stake(&mut self, position: &mut Position, amount: u64) -> Result<()> {
+        self.idle_amount = self.idle_amount.checked_sub(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.update_position(position, |position| position.stake(amount))
+    }
+
+    // 持仓发起赎回
+    pub fn request_unstake(&mut self, position: &mut Position, value: u64, principal: u64) -> Result<()> {
+        self.update_position(position, |position| position.request_unstake(value, principal))
+    }
+
+    // 持仓取消赎回，返回恢复的质押本金
+    pub fn cancel_unstake(&mut self, position: &mut Position, value: u64) -> Result<u64> {
+        self.update_position(position, |position| position.cancel_unstake(value))
+    }
+
+    // 持仓完成赎回，实际到账数量计入闲置资金
+    pub fn complete_unstake(&mut self, position: &mut Position, value: u64, received: u64) -> Result<()> {
+        self.update_position(position, |position| position.complete_unstake(value, received))?;
+        self.idle_amount = self.idle_amount.checked_add(received)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    // 按最新的持仓价值更新持仓，返回持仓未实现盈亏的变化量
+    pub fn mark_staked_value(&mut self, position: &mut Position, staked_value: u64) -> Result<i64> {
+        self.update_position(position, |position| position.mark_staked_value(staked_value))
+    }
+
+    // 修改持仓，并把持仓的变化量同步到资金账户，保证资金账户的质押数据等于所有持仓之和
+    fn update_position<T>(
+        &mut self,
+        position: &mut Position,
+        update: impl FnOnce(&mut Position) -> Result<T>,
+    ) -> Result<T> {
+        let before = position.clone();
+        let result = update(position)?;
+
+        self.staked_amount = apply_delta(self.staked_amount, before.staked_amount, position.staked_amount)?;
+        self.unstaking_amount = apply_delta(self.unstaking_amount, before.unstaking_amount, position.unstaking_amount)?;
+        self.staked_value = apply_delta(self.staked_value, before.staked_value, position.staked_value)?;
+        self.unrealized_pnl = self.unrealized_pnl
+            .checked_add(position.unrealized_pnl.checked_sub(before.unrealized_pnl).ok_or(ErrorCode::MathOverflow)?)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.realized_yield = self.realized_yield
+            .checked_add(position.realized_yield.checked_sub(before.realized_yield).ok_or(ErrorCode::MathOverflow)?)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(result)
+    }
+}
+
+// 单个drift金库的持仓，按 [position, fund_account, vault] 派生
+#[account]
+pub struct Position {
+    // 所属资金账户
+    pub fund_account: Pubkey,
+    // Drift金库
+    pub vault: Pubkey,
+    // 全局状态在该金库的存款人账户，通过 register_vault 登记
+    pub vault_depositor: Pubkey,
+    // 质押本金
+    pub staked_amount: u64,
+    // 赎回中的资金数量（赎回请求的价值）
+    pub unstaking_amount: u64,
+    // 赎回中的资金对应的质押本金，取消赎回时按此恢复本金并冲回发起赎回时计入的已实现收益
+    pub unstaking_principal: u64,
+    // 最近一次按市值计算的持仓价值
+    pub staked_value: u64,
+    // 未实现盈亏（持仓价值 - 质押本金）
+    pub unrealized_pnl: i64,
+    // 累计已实现收益（赎回时确认，亏损为负）
+    pub realized_yield: i64,
+    // PDA bump
+    pub bump: u8,
+}
+
+impl Position {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // fund_account
+        32 + // vault
+        32 + // vault_depositor
+        8 + // staked_amount
+        8 + // unstaking_amount
+        8 + // unstaking_principal
+        8 + // staked_value
+        8 + // unrealized_pnl
+        8 + // realized_yield
+        1; // bump
+
+    // 按份额比例计算赎回请求对应的质押本金
+    pub fn principal_for_shares(&self, request_shares: u128, total_shares: u128) -> Result<u64> {
+        if total_shares == 0 {
+            return Ok(0);
+        }
+        let principal = (self.staked_amount as u128)
+            .checked_mul(request_shares.min(total_shares))
+            .ok_or(ErrorCode::MathOverflow)?
+            / total_shares;
+        u64::try_from(principal).map_err(|_| error!(ErrorCode::MathOverflow))
+    }
+
+    // 按最新的持仓价值更新未实现盈亏，返回未实现盈亏的变化量
+    pub fn mark_staked_value(&mut self, staked_value: u64) -> Result<i64> {
+        let unrealized_pnl = to_i64(staked_value)?
+            .checked_sub(to_i64(self.staked_amount)?)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let delta = unrealized_pnl.checked_sub(self.unrealized_pnl)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.staked_value = staked_value;
+        self.unrealized_pnl = unrealized_pnl;
+        Ok(delta)
+    }
+
+    // 质押资金，持仓价值按本金同步增加
+    pub fn stake(&mut self, amount: u64) -> Result<()> {
+        self.staked_amount = self.staked_amount.checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let staked_value = self.staked_value.checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.mark_staked_value(staked_value)?;
+        Ok(())
+    }
+
+    // 发起赎回，value 为赎回请求的价值，principal 为其对应的质押本金，两者之差计入已实现收益
+    pub fn request_unstake(&mut self, value: u64, principal: u64) -> Result<()> {
+        self.staked_amount = self.staked_amount.checked_sub(principal)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.unstaking_amount = self.unstaking_amount.checked_add(value)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.unstaking_principal = self.unstaking_principal.checked_add(principal)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.record_realized_yield(value, principal)?;
+        self.mark_staked_value(self.staked_value.saturating_sub(value))?;
+        Ok(())
+    }
+
+    // 取消赎回，赎回中的资金按请求价值回到持仓价值，本金按发起赎回时扣除的数量恢复，
+    // 并冲回发起赎回时计入的已实现收益，避免再次赎回时重复确认同一部分收益，返回恢复的本金
+    pub fn cancel_unstake(&mut self, value: u64) -> Result<u64> {
+        let principal = self.release_unstaking(value)?;
+        self.staked_amount = self.staked_amount.checked_add(principal)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.record_realized_yield(principal, value)?;
+        let staked_value = self.staked_value.checked_add(value)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.mark_staked_value(staked_value)?;
+        Ok(principal)
+    }
+
+    // 完成赎回，与请求价值的差额计入已实现收益
+    pub fn complete_unstake(&mut self, value: u64, received: u64) -> Result<()> {
+        self.release_unstaking(value)?;
+        self.record_realized_yield(received, value)?;
+        Ok(())
+    }
+
+    // 从赎回中的资金移除 value，返回其对应的质押本金
+    fn release_unstaking(&mut self, value: u64) -> Result<u64> {
+        let principal = if value == self.unstaking_amount {
+            self.unstaking_principal
+        } else {
+            let principal = (self.unstaking_principal as u128)
+                .checked_mul(value as u128)
+                .ok_or(ErrorCode::MathOverflow)?
+                .checked_div(self.unstaking_amount as u128)
+                .ok_or(ErrorCode::MathOverflow)?;
+            u64::try_from(principal).map_err(|_| error!(ErrorCode::MathOverflow))?
+        };
+        self.unstaking_amount = self.unstaking_amount.checked_sub(value)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.unstaking_principal = self.unstaking_principal.checked_sub(principal)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(principal)
+    }
+
+    fn record_realized_yield(&mut self, amount: u64, cost: u64) -> Result<()> {
+        let realized = to_i64(amount)?
+            .checked_sub(to_i64(cost)?)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.realized_yield = self.realized_yield.checked_add(realized)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+}
+
+// 单个drift金库的分配目标
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Allocation {
+    // Drift金库
+    pub vault: Pubkey,
+    // 目标权重，单位为基点（万分之一）
+    pub weight_bps: u16,
+    // 持仓价值上限，0表示不限制
+    pub cap: u64,
+}
+
+impl Allocation {
+    pub const LEN: usize = 32 + 2 + 8;
+}
+
+// 资金账户在多个drift金库之间的分配配置，按 [allocation_config, fund_account] 派生
+#[account]
+pub struct AllocationConfig {
+    // 所属资金账户
+    pub fund_account: Pubkey,
+    // 各金库的分配目标
+    pub allocations: Vec<Allocation>,
+    // PDA bump
+    pub bump: u8,
+}
+
+impl AllocationConfig {
+    // 最多可配置的金库数量
+    pub const MAX_ALLOCATIONS: usize = 8;
+    // 权重的基点分母
+    pub const BPS_DENOMINATOR: u16 = 10_000;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // fund_account
+        4 + Self::MAX_ALLOCATIONS * Allocation::LEN + // allocations
+        1; // bump
+
+    // 查找金库的分配目标
+    pub fn allocation(&self, vault: &Pubkey) -> Result<&Allocation> {
+        self.allocations
+            .iter()
+            .find(|allocation| &allocation.vault == vault)
+            .ok_or(error!(ErrorCode::AllocationNotFound))
+    }
+
+    // 新增或更新金库的分配目标，所有金库的权重之和不能超过100%
+    pub fn set_allocation(&mut self, vault: Pubkey, weight_bps: u16, cap: u64) -> Result<()> {
+        require!(vault != Pubkey::default(), ErrorCode::InvalidArgument);
+
+        let total_weight = self.allocations
+            .iter()
+            .filter(|allocation| allocation.vault != vault)
+            .map(|allocation| allocation.weight_bps as u32)
+            .sum::<u32>()
+            + weight_bps as u32;
+        require!(
+            total_weight <= Self::BPS_DENOMINATOR as u32,
+            ErrorCode::InvalidAllocationWeight
+        );
+
+        let allocation = Allocation { vault, weight_bps, cap };
+        match self.allocations.iter_mut().find(|allocation| allocation.vault == vault) {
+            Some(existing) => *existing = allocation,
+            None => {
+                require!(
+                    self.allocations.len() < Self::MAX_ALLOCATIONS,
+                    ErrorCode::TooManyAllocations
+                );
+                self.allocations.push(allocation);
+            }
+        }
+        Ok(())
+    }
+
+    // 金库的目标持仓价值：总价值按权重分配，并受上限约束
+    pub fn target_value(&self, vault: &Pubkey, total_value: u64) -> Result<u64> {
+        let allocation = self.allocation(vault)?;
+        let target = (total_value as u128)
+            .checked_mul(allocation.weight_bps as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            / Self::BPS_DENOMINATOR as u128;
+        let target = u64::try_from(target).map_err(|_| error!(ErrorCode::MathOverflow))?;
+        Ok(if allocation.cap > 0 { target.min(allocation.cap) } else { target })
+    }
+
+    // 检查质押后的持仓价值是否超过金库上限
+    pub fn check_cap(&self, vault: &Pubkey, staked_value: u64) -> Result<()> {
+        let allocation = self.allocation(vault)?;
+        require!(
+            allocation.cap == 0 || staked_value <= allocation.cap,
+            ErrorCode::AllocationCapExceeded
+        );
+        Ok(())
+    }
+
+    // 再平衡时应质押到金库的数量：补足与目标的差额，不超过闲置资金
+    pub fn rebalance_amount(
+        &self,
+        vault: &Pubkey,
+        total_value: u64,
+        staked_value: u64,
+        idle_amount: u64,
+    ) -> Result<u64> {
+        let target = self.target_value(vault, total_value)?;
+        Ok(target.saturating_sub(staked_value).min(idle_amount))
+    }
+}
+
+fn apply_delta(total: u64, before: u64, after: u64) -> Result<u64> {
+    total
+        .checked_sub(before)
+        .and_then(|total| total.checked_add(after))
+        .ok_or(error!(ErrorCode::MathOverflow))
+}
+
+fn to_i64(amount: u64) -> Result<i64> {
+    i64::try_from(amount).map_err(|_| error!(ErrorCode::MathOverflow))
+}
+
+// 角色枚举，管理员通过 grant_role / revoke_role 授予或撤销
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    // 操作员，可以质押、赎回和跨链转出资金
+    Operator,
+    // 暂停者，可以暂停和恢复消息传输器
+    Pauser,
+    // 中继者，可以提交跨链消息
+    Relayer,
+}
+
+// 权限控制账户，按 [access_control] 派生
+#[account]
+pub struct AccessControl {
+    // 管理员
+    pub admin: Pubkey,
+    // 待接受的新管理员，两步转移
+    pub pending_admin: Pubkey,
+    // 操作员列表
+    pub operators: Vec<Pubkey>,
+    // 暂停者列表
+    pub pausers: Vec<Pubkey>,
+    // 中继者列表
+    pub relayers: Vec<Pubkey>,
+    // PDA bump
+    pub bump: u8,
+}
+
+impl AccessControl {
+    // 每个角色最多的成员数量
+    pub const MAX_ROLE_MEMBERS: usize = 8;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // admin
+        32 + // pending_admin
+        3 * (4 + Self::MAX_ROLE_MEMBERS * 32) + // operators, pausers, relayers
+        1; // bump
+
+    fn members(&self, role: Role) -> &Vec<Pubkey> {
+        match role {
+            Role::Operator => &self.operators,
+            Role::Pauser => &self.pausers,
+            Role::Relayer => &self.relayers,
+        }
+    }
+
+    fn members_mut(&mut self, role: Role) -> &mut Vec<Pubkey> {
+        match role {
+            Role::Operator => &mut self.operators,
+            Role::Pauser => &mut self.pausers,
+            Role::Relayer => &mut self.relayers,
+        }
+    }
+
+    // 判断账户是否拥有角色
+    pub fn has_role(&self, role: Role, account: &Pubkey) -> bool {
+        self.members(role).contains(account)
+    }
+
+    // 授予角色
+    pub fn grant_role(&mut self, role: Role, account: Pubkey) -> Result<()> {
+        require!(account != Pubkey::default(), ErrorCode::InvalidArgument);
+        require!(!self.has_role(role, &account), ErrorCode::RoleAlreadyGranted);
+
+        let members = self.members_mut(role);
+        require!(
+            members.len() < Self::MAX_ROLE_MEMBERS,
+            ErrorCode::TooManyRoleMembers
+        );
+        members.push(account);
+        Ok(())
+    }
+
+    // 撤销角色
+    pub fn revoke_role(&mut self, role: Role, account: Pubkey) -> Result<()> {
+        let members = self.members_mut(role);
+        let index = members
+            .iter()
+            .position(|member| member == &account)
+            .ok_or(ErrorCode::RoleNotGranted)?;
+        members.remove(index);
+        Ok(())
+    }
+}
+
+// 角色授予事件
+#[event]
+pub struct RoleGranted {
+    // 角色
+    pub role: Role,
+    // 被授予的账户
+    pub account: Pubkey,
+    // 执行授予的管理员
+    pub admin: Pubkey,
+}
+
+// 角色撤销事件
+#[event]
+pub struct RoleRevoked {
+    // 角色
+    pub role: Role,
+    // 被撤销的账户
+    pub account: Pubkey,
+    // 执行撤销的管理员
+    pub admin: Pubkey,
+}
+
+// 权限控制管理员转移发起事件
+#[event]
+pub struct AdminTransferStarted {
+    // 当前管理员
+    pub previous_admin: Pubkey,
+    // 待接受的新管理员
+    pub new_admin: Pubkey,
+}
+
+// 权限控制管理员转移完成事件
+#[event]
+pub struct AdminTransferred {
+    // 原管理员
+    pub previous_admin: Pubkey,
+    // 新管理员
+    pub new_admin: Pubkey,
+}
+
+// 程序级暂停状态变更事件
+#[event]
+pub struct PauseStateChanged {
+    // 变更前已暂停的操作
+    pub previous_paused_operations: u8,
+    // 变更后已暂停的操作
+    pub paused_operations: u8,
+    // 执行变更的暂停者
+    pub authority: Pubkey,
+    // 时间戳
+    pub timestamp: i64,
+}
+
+// 质押收益计提事件
+#[event]
+pub struct YieldAccrued {
+    // 相关代币账户
+    pub token_account: Pubkey,
+    // 质押本金
+    pub staked_amount: u64,
+    // 按市值计算的质押持仓价值
+    pub staked_value: u64,
+    // 未实现盈亏
+    pub unrealized_pnl: i64,
+    // 本次同步的未实现盈亏变化量
+    pub unrealized_pnl_delta: i64,
+    // 累计已实现收益
+    pub realized_yield: i64,
+    // 时间戳
+    pub timestamp: i64,
+}
+
+// 金库分配目标变更事件
+#[event]
+pub struct AllocationUpdated {
+    // 资金账户
+    pub fund_account: Pubkey,
+    // Drift金库
+    pub vault: Pubkey,
+    // 目标权重，单位为基点
+    pub weight_bps: u16,
+    // 持仓价值上限，0表示不限制
+    pub cap: u64,
+}
+
+// 再平衡事件
+#[event]
+pub struct Rebalanced {
+    // 资金账户
+    pub fund_account: Pubkey,
+    // Drift金库
+    pub vault: Pubkey,
+    // 本次质押到金库的数量
+    pub amount: u64,
+    // 金库的目标持仓价值
+    pub target_value: u64,
+    // 质押后的持仓价值
+    pub staked_value: u64,
+    // 时间戳
+    pub timestamp: i64,
+}
+
+// 金库登记事件
+#[event]
+pub struct VaultRegistered {
+    // 资金账户
+    pub fund_account: Pubkey,
+    // Drift金库
+    pub vault: Pubkey,
+    // 新建的金库存款人账户
+    pub vault_depositor: Pubkey,
 }
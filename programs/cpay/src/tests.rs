@@ -0,0 +1,880 @@
+#[cfg(test)]
+mod attester_tests {
+    use crate::circle::{recover_attester, MessageTransmitter, SIGNATURE_LENGTH};
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::solana_program::keccak;
+    use libsecp256k1::{sign, Message, PublicKey, SecretKey};
+
+    fn secret_key(seed: u8) -> SecretKey {
+        SecretKey::parse(&[seed; 32]).unwrap()
+    }
+
+    fn attester_address(secret_key: &SecretKey) -> Pubkey {
+        let pubkey = PublicKey::from_secret_key(secret_key).serialize();
+        let mut address = keccak::hash(&pubkey[1..]).to_bytes();
+        address[..12].fill(0);
+        Pubkey::new_from_array(address)
+    }
+
+    fn sign_message(message: &[u8], secret_key: &SecretKey) -> [u8; SIGNATURE_LENGTH] {
+        let digest = keccak::hash(message).to_bytes();
+        let (signature, recovery_id) = sign(&Message::parse(&digest), secret_key);
+        let mut out = [0u8; SIGNATURE_LENGTH];
+        out[..64].copy_from_slice(&signature.serialize());
+        out[64] = recovery_id.serialize() + 27;
+        out
+    }
+
+    // 按验证人地址升序签名
+    fn sign_sorted(message: &[u8], secret_keys: &[SecretKey]) -> Vec<[u8; SIGNATURE_LENGTH]> {
+        let mut keys = secret_keys.to_vec();
+        keys.sort_by_key(attester_address);
+        keys.iter().map(|key| sign_message(message, key)).collect()
+    }
+
+    fn transmitter(secret_keys: &[SecretKey], threshold: u32) -> MessageTransmitter {
+        let mut message_transmitter = MessageTransmitter::default();
+        for key in secret_keys {
+            message_transmitter
+                .enable_attester(attester_address(key))
+                .unwrap();
+        }
+        message_transmitter
+            .set_signature_threshold(threshold)
+            .unwrap();
+        message_transmitter
+    }
+
+    #[test]
+    fn test_recover_attester() {
+        let key = secret_key(1);
+        let message = b"cpay attestation";
+        let signature = sign_message(message, &key);
+        let digest = keccak::hash(message).to_bytes();
+
+        assert_eq!(
+            recover_attester(&digest, &signature).unwrap(),
+            attester_address(&key)
+        );
+
+        let mut bad_v = signature;
+        bad_v[64] = 1;
+        assert!(recover_attester(&digest, &bad_v).is_err());
+
+        // 高 s 值签名被拒绝
+        let mut high_s = signature;
+        high_s[32] = 0xff;
+        assert!(recover_attester(&digest, &high_s).is_err());
+    }
+
+    #[test]
+    fn test_verify_threshold_signatures() {
+        let keys = [secret_key(1), secret_key(2), secret_key(3)];
+        let message_transmitter = transmitter(&keys, 2);
+        let message = b"cpay attestation";
+
+        let signatures = sign_sorted(message, &keys[..2]);
+        message_transmitter
+            .verify_attestation_signatures(message, &signatures)
+            .unwrap();
+
+        // 签名数量必须等于阈值
+        assert!(message_transmitter
+            .verify_attestation_signatures(message, &signatures[..1])
+            .is_err());
+        let all_signatures = sign_sorted(message, &keys);
+        assert!(message_transmitter
+            .verify_attestation_signatures(message, &all_signatures)
+            .is_err());
+
+        // 签名对应的消息必须一致
+        assert!(message_transmitter
+            .verify_attestation_signatures(b"other message", &signatures)
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_unsorted_and_duplicate_signatures() {
+        let keys = [secret_key(1), secret_key(2)];
+        let message_transmitter = transmitter(&keys, 2);
+        let message = b"cpay attestation";
+
+        let mut signatures = sign_sorted(message, &keys);
+        signatures.reverse();
+        assert!(message_transmitter
+            .verify_attestation_signatures(message, &signatures)
+            .is_err());
+
+        let duplicated = vec![signatures[0], signatures[0]];
+        assert!(message_transmitter
+            .verify_attestation_signatures(message, &duplicated)
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_disabled_attester() {
+        let keys = [secret_key(1), secret_key(2)];
+        let mut message_transmitter = transmitter(&keys, 1);
+        let message = b"cpay attestation";
+
+        let outsider = sign_message(message, &secret_key(9));
+        assert!(message_transmitter
+            .verify_attestation_signatures(message, &[outsider])
+            .is_err());
+
+        let signature = sign_message(message, &keys[0]);
+        message_transmitter
+            .verify_attestation_signatures(message, &[signature])
+            .unwrap();
+
+        message_transmitter
+            .disable_attester(attester_address(&keys[0]))
+            .unwrap();
+        assert!(message_transmitter
+            .verify_attestation_signatures(message, &[signature])
+            .is_err());
+    }
+
+    #[test]
+    fn test_attester_administration() {
+        let keys = [secret_key(1), secret_key(2)];
+        let mut message_transmitter = transmitter(&keys, 2);
+
+        // 不能重复启用
+        assert!(message_transmitter
+            .enable_attester(attester_address(&keys[0]))
+            .is_err());
+        assert!(message_transmitter
+            .enable_attester(Pubkey::default())
+            .is_err());
+
+        // 阈值不能为0，也不能超过验证人数量
+        assert!(message_transmitter.set_signature_threshold(0).is_err());
+        assert!(message_transmitter.set_signature_threshold(3).is_err());
+
+        // 禁用后验证人数量不能低于阈值
+        assert!(message_transmitter
+            .disable_attester(attester_address(&keys[0]))
+            .is_err());
+        message_transmitter.set_signature_threshold(1).unwrap();
+        message_transmitter
+            .disable_attester(attester_address(&keys[0]))
+            .unwrap();
+        assert!(message_transmitter
+            .disable_attester(attester_address(&keys[0]))
+            .is_err());
+
+        // 最后一个验证人不能被禁用
+        assert!(message_transmitter
+            .disable_attester(attester_address(&keys[1]))
+            .is_err());
+
+        for seed in 10..(10 + MessageTransmitter::MAX_ATTESTERS as u8 - 1) {
+            message_transmitter
+                .enable_attester(attester_address(&secret_key(seed)))
+                .unwrap();
+        }
+        assert!(message_transmitter
+            .enable_attester(attester_address(&secret_key(100)))
+            .is_err());
+    }
+}
+
+#[cfg(test)]
+mod used_nonces_tests {
+    use crate::circle::UsedNonces;
+
+    #[test]
+    fn test_first_nonce() {
+        assert_eq!(UsedNonces::first_nonce(0), 0);
+        assert_eq!(UsedNonces::first_nonce(6399), 0);
+        assert_eq!(UsedNonces::first_nonce(6400), 6400);
+        assert_eq!(UsedNonces::first_nonce(12_801), 12_800);
+    }
+
+    #[test]
+    fn test_use_nonce() {
+        let mut used_nonces = UsedNonces {
+            remote_domain: 0,
+            first_nonce: UsedNonces::first_nonce(6400),
+            ..UsedNonces::default()
+        };
+
+        assert!(!used_nonces.is_nonce_used(6400).unwrap());
+        used_nonces.use_nonce(6400).unwrap();
+        assert!(used_nonces.is_nonce_used(6400).unwrap());
+        assert!(!used_nonces.is_nonce_used(6401).unwrap());
+
+        // 重复投递失败
+        assert!(used_nonces.use_nonce(6400).is_err());
+
+        used_nonces.use_nonce(12_799).unwrap();
+        assert!(used_nonces.is_nonce_used(12_799).unwrap());
+
+        // 超出账户覆盖范围
+        assert!(used_nonces.use_nonce(6399).is_err());
+        assert!(used_nonces.use_nonce(12_800).is_err());
+    }
+}
+
+#[cfg(test)]
+mod message_tests {
+    use crate::circle::{BurnMessageBody, Message};
+
+    fn burn_message_body() -> BurnMessageBody {
+        BurnMessageBody {
+            version: 0,
+            burn_token: [1; 32],
+            mint_recipient: [2; 32],
+            amount: 1_000_000,
+            message_sender: [3; 32],
+        }
+    }
+
+    fn message() -> Message {
+        Message {
+            version: 0,
+            source_domain: 0,
+            destination_domain: 5,
+            nonce: 42,
+            sender: [4; 32],
+            recipient: [5; 32],
+            destination_caller: [0; 32],
+            message_body: burn_message_body().serialize(),
+        }
+    }
+
+    #[test]
+    fn test_message_layout() {
+        let bytes = message().serialize();
+        assert_eq!(bytes.len(), Message::HEADER_LEN + BurnMessageBody::LEN);
+
+        // 大端序字段位置
+        assert_eq!(bytes[0..4], [0, 0, 0, 0]);
+        assert_eq!(bytes[8..12], [0, 0, 0, 5]);
+        assert_eq!(bytes[12..20], [0, 0, 0, 0, 0, 0, 0, 42]);
+        assert_eq!(bytes[20..52], [4; 32]);
+        assert_eq!(bytes[52..84], [5; 32]);
+        assert_eq!(bytes[84..116], [0; 32]);
+
+        let body = &bytes[Message::HEADER_LEN..];
+        assert_eq!(body[4..36], [1; 32]);
+        assert_eq!(body[36..68], [2; 32]);
+        assert_eq!(body[68..92], [0; 24]);
+        assert_eq!(body[92..100], 1_000_000u64.to_be_bytes());
+        assert_eq!(body[100..132], [3; 32]);
+
+        assert_eq!(Message::source_domain_of(&bytes), 0);
+        assert_eq!(Message::nonce_of(&bytes), 42);
+        assert_eq!(Message::burn_token_of(&bytes), [1; 32]);
+        assert_eq!(Message::burn_token_of(&bytes[..bytes.len() - 1]), [0; 32]);
+    }
+
+    #[test]
+    fn test_message_round_trip() {
+        let bytes = message().serialize();
+        let parsed = Message::parse(&bytes).unwrap();
+        assert_eq!(parsed, message());
+        assert_eq!(
+            BurnMessageBody::parse(&parsed.message_body).unwrap(),
+            burn_message_body()
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_messages() {
+        let bytes = message().serialize();
+        assert!(Message::parse(&bytes[..Message::HEADER_LEN - 1]).is_err());
+        assert_eq!(Message::nonce_of(&bytes[..Message::HEADER_LEN - 1]), 0);
+
+        let body = burn_message_body().serialize();
+        assert!(BurnMessageBody::parse(&body[..BurnMessageBody::LEN - 1]).is_err());
+
+        let mut long_body = body.clone();
+        long_body.push(0);
+        assert!(BurnMessageBody::parse(&long_body).is_err());
+
+        // 超出 u64 的 uint256 数量
+        let mut overflow = body;
+        overflow[91] = 1;
+        assert!(BurnMessageBody::parse(&overflow).is_err());
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        // 伪随机输入不能导致解析 panic
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        for len in 0..400 {
+            let bytes: Vec<u8> = (0..len)
+                .map(|_| {
+                    seed ^= seed << 13;
+                    seed ^= seed >> 7;
+                    seed ^= seed << 17;
+                    seed as u8
+                })
+                .collect();
+
+            if let Ok(message) = Message::parse(&bytes) {
+                assert_eq!(message.serialize(), bytes);
+                if let Ok(body) = BurnMessageBody::parse(&message.message_body) {
+                    assert_eq!(body.serialize(), message.message_body);
+                }
+            }
+            let _ = BurnMessageBody::parse(&bytes);
+            let _ = Message::nonce_of(&bytes);
+            let _ = Message::source_domain_of(&bytes);
+            let _ = Message::burn_token_of(&bytes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod burn_limits_tests {
+    use crate::circle::{TokenMessenger, DEFAULT_BURN_WINDOW_DURATION};
+
+    #[test]
+    fn test_no_limits_by_default() {
+        let mut token_messenger = TokenMessenger::default();
+        token_messenger.record_burn(u64::MAX, 0).unwrap();
+        token_messenger.record_burn(u64::MAX, 0).unwrap();
+    }
+
+    #[test]
+    fn test_per_message_limit() {
+        let mut token_messenger = TokenMessenger::default();
+        token_messenger
+            .set_burn_limits(1_000_000, 0, DEFAULT_BURN_WINDOW_DURATION, 0)
+            .unwrap();
+
+        token_messenger.record_burn(1_000_000, 0).unwrap();
+        assert!(token_messenger.record_burn(1_000_001, 0).is_err());
+    }
+
+    #[test]
+    fn test_window_limit() {
+        let now = 1_000;
+        let mut token_messenger = TokenMessenger::default();
+        token_messenger
+            .set_burn_limits(0, 10_000_000, DEFAULT_BURN_WINDOW_DURATION, now)
+            .unwrap();
+
+        token_messenger.record_burn(6_000_000, now).unwrap();
+        token_messenger.record_burn(4_000_000, now).unwrap();
+        assert!(token_messenger.record_burn(1_000_000, now + 1).is_err());
+
+        // 窗口结束前已销毁数量不会减少
+        let half_window = now + DEFAULT_BURN_WINDOW_DURATION / 2;
+        assert_eq!(token_messenger.burned_in_window_at(half_window), 10_000_000);
+        assert!(token_messenger.record_burn(1, half_window).is_err());
+
+        // 窗口结束后限额完全恢复
+        let next_window = now + DEFAULT_BURN_WINDOW_DURATION;
+        assert_eq!(token_messenger.burned_in_window_at(next_window), 0);
+        token_messenger.record_burn(10_000_000, next_window).unwrap();
+        assert!(token_messenger.record_burn(1, next_window + 1).is_err());
+    }
+
+    #[test]
+    fn test_window_total_within_limit() {
+        let limit = 10_000_000;
+        let mut token_messenger = TokenMessenger::default();
+        token_messenger
+            .set_burn_limits(0, limit, DEFAULT_BURN_WINDOW_DURATION, 0)
+            .unwrap();
+
+        // 同一窗口内多次销毁，累计数量不超过限额
+        let mut total = 0;
+        for i in 0..8 {
+            let now = i * DEFAULT_BURN_WINDOW_DURATION / 8;
+            if token_messenger.record_burn(3_000_000, now).is_ok() {
+                total += 3_000_000;
+            }
+            assert!(total <= limit);
+        }
+        assert_eq!(total, 9_000_000);
+        token_messenger.record_burn(1_000_000, DEFAULT_BURN_WINDOW_DURATION - 1).unwrap();
+        assert!(token_messenger.record_burn(1, DEFAULT_BURN_WINDOW_DURATION - 1).is_err());
+
+        // 新窗口从首次销毁时间开始计算
+        let next_window = DEFAULT_BURN_WINDOW_DURATION + 100;
+        token_messenger.record_burn(limit, next_window).unwrap();
+        assert!(token_messenger
+            .record_burn(1, next_window + DEFAULT_BURN_WINDOW_DURATION - 1)
+            .is_err());
+        token_messenger
+            .record_burn(1, next_window + DEFAULT_BURN_WINDOW_DURATION)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_set_burn_limits_validation() {
+        let mut token_messenger = TokenMessenger::default();
+        assert!(token_messenger.set_burn_limits(0, 1, 0, 0).is_err());
+        token_messenger.set_burn_limits(0, 0, 0, 0).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod global_state_tests {
+    use crate::instructions::{GlobalState, Treasury};
+    use anchor_lang::prelude::Pubkey;
+
+    fn global_state() -> GlobalState {
+        GlobalState {
+            initialized: true,
+            bump: 255,
+            paused_operations: 0,
+            total_funds: 0,
+            staked_funds: 0,
+            unstaking_funds: 0,
+            idle_funds: 0,
+            treasuries: Vec::new(),
+        }
+    }
+
+    fn treasury() -> Treasury {
+        Treasury {
+            mint: Pubkey::new_unique(),
+            token_account: Pubkey::new_unique(),
+            fund_account: Pubkey::new_unique(),
+        }
+    }
+
+    #[test]
+    fn test_add_treasury() {
+        let mut global_state = global_state();
+        let usdc = treasury();
+        let usdt = treasury();
+
+        global_state.add_treasury(usdc).unwrap();
+        global_state.add_treasury(usdt).unwrap();
+        assert_eq!(global_state.treasury(&usdc.mint), Some(&usdc));
+        assert_eq!(global_state.treasury(&usdt.mint), Some(&usdt));
+        assert!(global_state.treasury(&Pubkey::new_unique()).is_none());
+
+        // 同一代币不能重复登记
+        assert!(global_state.add_treasury(usdc).is_err());
+
+        // 金库数量有上限
+        while global_state.treasuries.len() < GlobalState::MAX_TREASURIES {
+            global_state.add_treasury(treasury()).unwrap();
+        }
+        assert!(global_state.add_treasury(treasury()).is_err());
+    }
+
+    #[test]
+    fn test_aggregates() {
+        let mut global_state = global_state();
+
+        // 两个金库各自收到资金，汇总值累加
+        global_state.record_received(1_000).unwrap();
+        global_state.record_received(500).unwrap();
+        assert_eq!(global_state.idle_funds, 1_500);
+        assert_eq!(global_state.total_funds, 1_500);
+
+        global_state.record_staked(600).unwrap();
+        assert_eq!(global_state.idle_funds, 900);
+        assert_eq!(global_state.staked_funds, 600);
+        assert_eq!(global_state.total_funds, 1_500);
+
+        global_state.record_sent(400).unwrap();
+        assert_eq!(global_state.idle_funds, 500);
+        assert_eq!(global_state.total_funds, 1_100);
+
+        // 发起赎回后取消，资金回到质押
+        global_state.record_unstake_requested(100, 100).unwrap();
+        assert_eq!(global_state.staked_funds, 500);
+        assert_eq!(global_state.unstaking_funds, 100);
+        global_state.record_unstake_cancelled(100, 100).unwrap();
+        assert_eq!(global_state.staked_funds, 600);
+        assert_eq!(global_state.unstaking_funds, 0);
+
+        // 请求价值含收益，完成时实际到账少于请求价值
+        global_state.record_unstake_requested(700, 600).unwrap();
+        assert_eq!(global_state.staked_funds, 0);
+        assert_eq!(global_state.total_funds, 1_200);
+        global_state.record_unstake_completed(700, 650).unwrap();
+        assert_eq!(global_state.unstaking_funds, 0);
+        assert_eq!(global_state.idle_funds, 1_150);
+        assert_eq!(global_state.total_funds, 1_150);
+
+        // 不能超出现有余额
+        assert!(global_state.record_staked(1_151).is_err());
+        assert!(global_state.record_unstake_cancelled(1, 1).is_err());
+        assert!(global_state.record_sent(1_151).is_err());
+    }
+
+    #[test]
+    fn test_pause_flags() {
+        let mut global_state = global_state();
+        assert!(global_state.require_not_paused(GlobalState::PAUSE_ALL).is_ok());
+
+        global_state.set_paused(GlobalState::PAUSE_STAKE | GlobalState::PAUSE_BURN, true).unwrap();
+        assert!(global_state.require_not_paused(GlobalState::PAUSE_STAKE).is_err());
+        assert!(global_state.require_not_paused(GlobalState::PAUSE_BURN).is_err());
+        assert!(global_state.require_not_paused(GlobalState::PAUSE_UNSTAKE).is_ok());
+        assert!(global_state.require_not_paused(GlobalState::PAUSE_RECEIVE).is_ok());
+
+        // 总开关
+        global_state.set_paused(GlobalState::PAUSE_ALL, true).unwrap();
+        assert_eq!(global_state.paused_operations, GlobalState::PAUSE_ALL);
+
+        global_state.set_paused(GlobalState::PAUSE_RECEIVE, false).unwrap();
+        assert!(global_state.require_not_paused(GlobalState::PAUSE_RECEIVE).is_ok());
+        assert!(global_state.require_not_paused(GlobalState::PAUSE_UNSTAKE).is_err());
+
+        // 空集合和未知标志被拒绝
+        assert!(global_state.set_paused(0, true).is_err());
+        assert!(global_state.set_paused(1 << 7, true).is_err());
+    }
+}
+
+#[cfg(test)]
+mod access_control_tests {
+    use crate::state::{AccessControl, Role};
+    use anchor_lang::prelude::Pubkey;
+
+    fn access_control() -> AccessControl {
+        AccessControl {
+            admin: Pubkey::new_unique(),
+            pending_admin: Pubkey::default(),
+            operators: Vec::new(),
+            pausers: Vec::new(),
+            relayers: Vec::new(),
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_grant_and_revoke_role() {
+        let mut access_control = access_control();
+        let operator = Pubkey::new_unique();
+
+        // 管理员本身不隐含其他角色
+        assert!(!access_control.has_role(Role::Operator, &access_control.admin));
+
+        access_control.grant_role(Role::Operator, operator).unwrap();
+        assert!(access_control.has_role(Role::Operator, &operator));
+        assert!(!access_control.has_role(Role::Pauser, &operator));
+        assert!(!access_control.has_role(Role::Relayer, &operator));
+        assert!(access_control.grant_role(Role::Operator, operator).is_err());
+
+        // 同一账户可以拥有多个角色
+        access_control.grant_role(Role::Relayer, operator).unwrap();
+        assert!(access_control.has_role(Role::Relayer, &operator));
+
+        access_control.revoke_role(Role::Operator, operator).unwrap();
+        assert!(!access_control.has_role(Role::Operator, &operator));
+        assert!(access_control.has_role(Role::Relayer, &operator));
+        assert!(access_control.revoke_role(Role::Operator, operator).is_err());
+
+        assert!(access_control.grant_role(Role::Pauser, Pubkey::default()).is_err());
+    }
+
+    #[test]
+    fn test_role_member_limit() {
+        let mut access_control = access_control();
+        for _ in 0..AccessControl::MAX_ROLE_MEMBERS {
+            access_control.grant_role(Role::Pauser, Pubkey::new_unique()).unwrap();
+        }
+        assert!(access_control.grant_role(Role::Pauser, Pubkey::new_unique()).is_err());
+
+        // 上限按角色分别计算
+        access_control.grant_role(Role::Relayer, Pubkey::new_unique()).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod fund_account_tests {
+    use crate::instructions::GlobalState;
+    use crate::state::{FundAccount, Position};
+    use anchor_lang::prelude::Pubkey;
+
+    fn fund_account(idle_amount: u64) -> FundAccount {
+        FundAccount {
+            idle_amount,
+            staked_amount: 0,
+            unstaking_amount: 0,
+            sent_amount: 0,
+            total_received_amount: 0,
+            staked_value: 0,
+            unrealized_pnl: 0,
+            realized_yield: 0,
+            last_update_time: 0,
+            authority: Pubkey::new_unique(),
+        }
+    }
+
+    fn position() -> Position {
+        Position {
+            fund_account: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            vault_depositor: Pubkey::new_unique(),
+            staked_amount: 0,
+            unstaking_amount: 0,
+            unstaking_principal: 0,
+            staked_value: 0,
+            unrealized_pnl: 0,
+            realized_yield: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_principal_for_shares() {
+        let mut fund_account = fund_account(1_000);
+        let mut position = position();
+        fund_account.stake(&mut position, 1_000).unwrap();
+
+        assert_eq!(position.principal_for_shares(25, 100).unwrap(), 250);
+        assert_eq!(position.principal_for_shares(100, 100).unwrap(), 1_000);
+        // 请求份额不会超过持有份额
+        assert_eq!(position.principal_for_shares(150, 100).unwrap(), 1_000);
+        assert_eq!(position.principal_for_shares(0, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_mark_staked_value() {
+        let mut fund_account = fund_account(1_000);
+        let mut position = position();
+        fund_account.stake(&mut position, 1_000).unwrap();
+        assert_eq!(fund_account.staked_value, 1_000);
+        assert_eq!(fund_account.unrealized_pnl, 0);
+
+        assert_eq!(fund_account.mark_staked_value(&mut position, 1_100).unwrap(), 100);
+        assert_eq!(fund_account.unrealized_pnl, 100);
+        assert_eq!(position.unrealized_pnl, 100);
+
+        // 亏损时未实现盈亏为负
+        assert_eq!(fund_account.mark_staked_value(&mut position, 950).unwrap(), -150);
+        assert_eq!(fund_account.unrealized_pnl, -50);
+        assert_eq!(fund_account.realized_yield, 0);
+    }
+
+    #[test]
+    fn test_request_and_cancel_unstake() {
+        let mut fund_account = fund_account(1_000);
+        let mut position = position();
+        fund_account.stake(&mut position, 1_000).unwrap();
+
+        fund_account.request_unstake(&mut position, 400, 400).unwrap();
+        assert_eq!(fund_account.staked_amount, 600);
+        assert_eq!(fund_account.unstaking_amount, 400);
+        assert_eq!(fund_account.staked_value, 600);
+        assert_eq!(fund_account.realized_yield, 0);
+
+        fund_account.cancel_unstake(&mut position, 400).unwrap();
+        assert_eq!(fund_account.staked_amount, 1_000);
+        assert_eq!(fund_account.unstaking_amount, 0);
+        assert_eq!(fund_account.staked_value, 1_000);
+        assert!(fund_account.cancel_unstake(&mut position, 1).is_err());
+    }
+
+    #[test]
+    fn test_unstake_realizes_yield() {
+        let mut fund_account = fund_account(1_000);
+        let mut position = position();
+        fund_account.stake(&mut position, 1_000).unwrap();
+        fund_account.mark_staked_value(&mut position, 1_200).unwrap();
+
+        // 赎回一半份额，请求价值600对应本金500
+        fund_account.request_unstake(&mut position, 600, 500).unwrap();
+        assert_eq!(fund_account.staked_amount, 500);
+        assert_eq!(fund_account.staked_value, 600);
+        assert_eq!(fund_account.unrealized_pnl, 100);
+        assert_eq!(fund_account.realized_yield, 100);
+
+        // 赎回期内金库亏损，实际到账少于请求价值
+        fund_account.complete_unstake(&mut position, 600, 580).unwrap();
+        assert_eq!(fund_account.unstaking_amount, 0);
+        assert_eq!(fund_account.idle_amount, 580);
+        assert_eq!(fund_account.realized_yield, 80);
+        assert_eq!(position.realized_yield, 80);
+    }
+
+    #[test]
+    fn test_request_cancel_complete_unstake() {
+        let mut fund_account = fund_account(1_000);
+        let mut position = position();
+        fund_account.stake(&mut position, 1_000).unwrap();
+        fund_account.mark_staked_value(&mut position, 1_200).unwrap();
+
+        // 请求价值600对应本金500，发起赎回时确认100收益
+        fund_account.request_unstake(&mut position, 600, 500).unwrap();
+        assert_eq!(position.unstaking_principal, 500);
+        assert_eq!(fund_account.realized_yield, 100);
+
+        // 取消赎回恢复本金并冲回已确认的收益
+        fund_account.cancel_unstake(&mut position, 600).unwrap();
+        assert_eq!(position.unstaking_principal, 0);
+        assert_eq!(fund_account.staked_amount, 1_000);
+        assert_eq!(fund_account.unstaking_amount, 0);
+        assert_eq!(fund_account.staked_value, 1_200);
+        assert_eq!(fund_account.unrealized_pnl, 200);
+        assert_eq!(fund_account.realized_yield, 0);
+
+        // 再次赎回同样的份额只确认一次收益
+        fund_account.request_unstake(&mut position, 600, 500).unwrap();
+        fund_account.complete_unstake(&mut position, 600, 600).unwrap();
+        assert_eq!(position.unstaking_principal, 0);
+        assert_eq!(fund_account.staked_amount, 500);
+        assert_eq!(fund_account.staked_value, 600);
+        assert_eq!(fund_account.unrealized_pnl, 100);
+        assert_eq!(fund_account.realized_yield, 100);
+        assert_eq!(fund_account.idle_amount, 600);
+        assert_eq!(fund_account.total_value().unwrap(), 600 + 600);
+    }
+
+    // 单个资金账户时全局汇总值必须与资金账户一致
+    fn assert_aggregates(global_state: &GlobalState, fund_account: &FundAccount) {
+        assert_eq!(global_state.idle_funds, fund_account.idle_amount);
+        assert_eq!(global_state.staked_funds, fund_account.staked_amount);
+        assert_eq!(global_state.unstaking_funds, fund_account.unstaking_amount);
+        assert_eq!(
+            global_state.total_funds,
+            fund_account.idle_amount + fund_account.staked_amount + fund_account.unstaking_amount
+        );
+    }
+
+    #[test]
+    fn test_cancel_unstake_restores_aggregates() {
+        let mut global_state = GlobalState {
+            initialized: true,
+            bump: 255,
+            paused_operations: 0,
+            total_funds: 0,
+            staked_funds: 0,
+            unstaking_funds: 0,
+            idle_funds: 0,
+            treasuries: Vec::new(),
+        };
+        let mut fund_account = fund_account(1_000);
+        let mut position = position();
+        global_state.record_received(1_000).unwrap();
+        fund_account.stake(&mut position, 1_000).unwrap();
+        global_state.record_staked(1_000).unwrap();
+        assert_aggregates(&global_state, &fund_account);
+
+        // 盈利：请求价值600对应本金500
+        fund_account.mark_staked_value(&mut position, 1_200).unwrap();
+        fund_account.request_unstake(&mut position, 600, 500).unwrap();
+        global_state.record_unstake_requested(600, 500).unwrap();
+        assert_aggregates(&global_state, &fund_account);
+        assert_eq!(global_state.total_funds, 1_100);
+
+        let principal = fund_account.cancel_unstake(&mut position, 600).unwrap();
+        assert_eq!(principal, 500);
+        global_state.record_unstake_cancelled(600, principal).unwrap();
+        assert_aggregates(&global_state, &fund_account);
+        assert_eq!(global_state.staked_funds, 1_000);
+        assert_eq!(global_state.total_funds, 1_000);
+
+        // 亏损：请求价值400对应本金500
+        fund_account.mark_staked_value(&mut position, 800).unwrap();
+        fund_account.request_unstake(&mut position, 400, 500).unwrap();
+        global_state.record_unstake_requested(400, 500).unwrap();
+        assert_aggregates(&global_state, &fund_account);
+        assert_eq!(global_state.total_funds, 900);
+
+        let principal = fund_account.cancel_unstake(&mut position, 400).unwrap();
+        assert_eq!(principal, 500);
+        global_state.record_unstake_cancelled(400, principal).unwrap();
+        assert_aggregates(&global_state, &fund_account);
+        assert_eq!(global_state.staked_funds, 1_000);
+        assert_eq!(global_state.unstaking_funds, 0);
+        assert_eq!(global_state.total_funds, 1_000);
+    }
+
+    #[test]
+    fn test_totals_are_sum_of_positions() {
+        let mut fund_account = fund_account(3_000);
+        let mut first = position();
+        let mut second = position();
+
+        fund_account.stake(&mut first, 1_000).unwrap();
+        fund_account.stake(&mut second, 1_500).unwrap();
+        fund_account.mark_staked_value(&mut first, 1_100).unwrap();
+        fund_account.mark_staked_value(&mut second, 1_400).unwrap();
+        fund_account.request_unstake(&mut second, 700, 750).unwrap();
+
+        assert_eq!(fund_account.idle_amount, 500);
+        assert_eq!(fund_account.staked_amount, first.staked_amount + second.staked_amount);
+        assert_eq!(fund_account.unstaking_amount, first.unstaking_amount + second.unstaking_amount);
+        assert_eq!(fund_account.staked_value, first.staked_value + second.staked_value);
+        assert_eq!(fund_account.unrealized_pnl, first.unrealized_pnl + second.unrealized_pnl);
+        assert_eq!(fund_account.realized_yield, first.realized_yield + second.realized_yield);
+        assert_eq!(fund_account.total_value().unwrap(), 500 + 1_100 + 700 + 700);
+    }
+}
+
+#[cfg(test)]
+mod allocation_config_tests {
+    use crate::state::AllocationConfig;
+    use anchor_lang::prelude::Pubkey;
+
+    fn allocation_config() -> AllocationConfig {
+        AllocationConfig {
+            fund_account: Pubkey::new_unique(),
+            allocations: vec![],
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_set_allocation() {
+        let mut config = allocation_config();
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+
+        config.set_allocation(first, 6_000, 0).unwrap();
+        config.set_allocation(second, 4_000, 0).unwrap();
+        // 权重之和不能超过100%
+        assert!(config.set_allocation(Pubkey::new_unique(), 1, 0).is_err());
+
+        // 更新已有金库时按新权重重新计算
+        config.set_allocation(first, 5_000, 2_000).unwrap();
+        assert_eq!(config.allocations.len(), 2);
+        assert_eq!(config.allocation(&first).unwrap().weight_bps, 5_000);
+        assert_eq!(config.allocation(&first).unwrap().cap, 2_000);
+        assert!(config.allocation(&Pubkey::new_unique()).is_err());
+        assert!(config.set_allocation(Pubkey::default(), 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_too_many_allocations() {
+        let mut config = allocation_config();
+        for _ in 0..AllocationConfig::MAX_ALLOCATIONS {
+            config.set_allocation(Pubkey::new_unique(), 100, 0).unwrap();
+        }
+        assert!(config.set_allocation(Pubkey::new_unique(), 100, 0).is_err());
+    }
+
+    #[test]
+    fn test_target_value_and_cap() {
+        let mut config = allocation_config();
+        let vault = Pubkey::new_unique();
+        config.set_allocation(vault, 2_500, 0).unwrap();
+        assert_eq!(config.target_value(&vault, 10_000).unwrap(), 2_500);
+        config.check_cap(&vault, u64::MAX).unwrap();
+
+        // 目标持仓价值受金库上限约束
+        config.set_allocation(vault, 2_500, 1_000).unwrap();
+        assert_eq!(config.target_value(&vault, 10_000).unwrap(), 1_000);
+        config.check_cap(&vault, 1_000).unwrap();
+        assert!(config.check_cap(&vault, 1_001).is_err());
+    }
+
+    #[test]
+    fn test_rebalance_amount() {
+        let mut config = allocation_config();
+        let vault = Pubkey::new_unique();
+        config.set_allocation(vault, 5_000, 0).unwrap();
+
+        // 总价值10000，目标5000，已持仓3000，补足2000
+        assert_eq!(config.rebalance_amount(&vault, 10_000, 3_000, 4_000).unwrap(), 2_000);
+        // 不超过闲置资金
+        assert_eq!(config.rebalance_amount(&vault, 10_000, 3_000, 500).unwrap(), 500);
+        // 已超过目标时无需再平衡
+        assert_eq!(config.rebalance_amount(&vault, 10_000, 6_000, 4_000).unwrap(), 0);
+    }
+}
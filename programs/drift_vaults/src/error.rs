@@ -55,6 +55,16 @@ pub enum ErrorCode {
     VaultProtocolMissing,
     #[msg("InvalidTokenization")]
     InvalidTokenization,
+    #[msg("CrystallizationPeriodNotEnded")]
+    CrystallizationPeriodNotEnded,
+    #[msg("NotAllowlisted")]
+    NotAllowlisted,
+    #[msg("AllowlistEntryExpired")]
+    AllowlistEntryExpired,
+    #[msg("AllowlistDepositLimitExceeded")]
+    AllowlistDepositLimitExceeded,
+    #[msg("NoCrystallizationPeriod")]
+    NoCrystallizationPeriod,
 }
 
 impl From<DriftErrorCode> for ErrorCode {
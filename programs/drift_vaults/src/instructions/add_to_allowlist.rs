@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::constraints::is_manager_for_vault;
+use crate::error::ErrorCode;
+use crate::state::{AllowlistEntry, Vault};
+use crate::{validate, Size};
+
+pub fn add_to_allowlist(ctx: Context<AddToAllowlist>, params: AllowlistEntryParams) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    validate!(
+        params.expiry_ts == 0 || params.expiry_ts > now,
+        ErrorCode::AllowlistEntryExpired,
+        "allowlist entry expiry must be in the future"
+    )?;
+
+    let mut allowlist_entry = ctx.accounts.allowlist_entry.load_init()?;
+    allowlist_entry.vault = ctx.accounts.vault.key();
+    allowlist_entry.authority = *ctx.accounts.authority.key;
+    allowlist_entry.max_deposit_amount = params.max_deposit_amount;
+    allowlist_entry.expiry_ts = params.expiry_ts;
+    allowlist_entry.bump = ctx.bumps.allowlist_entry;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub struct AllowlistEntryParams {
+    pub max_deposit_amount: u64,
+    pub expiry_ts: i64,
+}
+
+#[derive(Accounts)]
+pub struct AddToAllowlist<'info> {
+    #[account(
+        constraint = is_manager_for_vault(&vault, &manager)?,
+    )]
+    pub vault: AccountLoader<'info, Vault>,
+    #[account(
+      init,
+      seeds = [b"allowlist_entry", vault.key().as_ref(), authority.key().as_ref()],
+      space = AllowlistEntry::SIZE,
+      bump,
+      payer = manager
+    )]
+    pub allowlist_entry: AccountLoader<'info, AllowlistEntry>,
+    /// CHECK: the vault depositor authority being allowlisted, doesn't need to sign
+    pub authority: AccountInfo<'info>,
+    #[account(mut)]
+    pub manager: Signer<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use drift::instructions::optional_accounts::AccountMaps;
+use drift::state::user::User;
+
+use crate::constraints::{is_user_for_vault, is_vault_for_vault_depositor};
+use crate::state::traits::VaultDepositorBase;
+use crate::{AccountMapProvider, Vault, VaultDepositor, VaultProtocolProvider};
+
+pub fn crystallize_fees<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CrystallizeFees<'info>>,
+) -> Result<()> {
+    let clock = &Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    let mut vault = ctx.accounts.vault.load_mut()?;
+    let mut vault_depositor = ctx.accounts.vault_depositor.load_mut()?;
+
+    vault_depositor.validate_crystallize_fees(&vault, now)?;
+
+    // backwards compatible: if last rem acct does not deserialize into [`VaultProtocol`] then it's a legacy vault.
+    let mut vp = ctx.vault_protocol();
+    vault.validate_vault_protocol(&vp)?;
+    let mut vp = vp.as_mut().map(|vp| vp.load_mut()).transpose()?;
+
+    let user = ctx.accounts.drift_user.load()?;
+    let spot_market_index = vault.spot_market_index;
+
+    let AccountMaps {
+        perp_market_map,
+        spot_market_map,
+        mut oracle_map,
+    } = ctx.load_maps(clock.slot, Some(spot_market_index), vp.is_some())?;
+
+    let vault_equity =
+        vault.calculate_equity(&user, &perp_market_map, &spot_market_map, &mut oracle_map)?;
+
+    vault_depositor.realize_profits(vault_equity, &mut vault, &mut vp, now)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CrystallizeFees<'info> {
+    #[account(mut)]
+    pub vault: AccountLoader<'info, Vault>,
+    #[account(
+        mut,
+        constraint = is_vault_for_vault_depositor(&vault_depositor, &vault)?
+    )]
+    pub vault_depositor: AccountLoader<'info, VaultDepositor>,
+    #[account(
+        constraint = is_user_for_vault(&vault, &drift_user.key())?
+    )]
+    pub drift_user: AccountLoader<'info, User>,
+}
@@ -10,7 +10,7 @@ use crate::constraints::{
 };
 use crate::drift_cpi::DepositCPI;
 use crate::error::ErrorCode;
-use crate::state::{Vault, VaultDepositor, VaultProtocolProvider};
+use crate::state::{AllowlistEntry, Vault, VaultDepositor, VaultProtocolProvider};
 use crate::token_cpi::TokenTransferCPI;
 use crate::{declare_vault_seeds, implement_deposit, validate, AccountMapProvider};
 
@@ -25,6 +25,23 @@ pub fn deposit<'c: 'info, 'info>(
 
     let mut vault_depositor = ctx.accounts.vault_depositor.load_mut()?;
 
+    let allowlist_entry = if vault.permissioned {
+        let allowlist_entry = ctx
+            .accounts
+            .allowlist_entry
+            .as_ref()
+            .ok_or(ErrorCode::NotAllowlisted)?
+            .load()?;
+        allowlist_entry.validate(
+            &ctx.accounts.vault.key(),
+            &vault_depositor.authority,
+            clock.unix_timestamp,
+        )?;
+        Some(allowlist_entry)
+    } else {
+        None
+    };
+
     // backwards compatible: if last rem acct does not deserialize into [`VaultProtocol`] then it's a legacy vault.
     let mut vp = ctx.vault_protocol();
     vault.validate_vault_protocol(&vp)?;
@@ -50,6 +67,10 @@ pub fn deposit<'c: 'info, 'info>(
         clock.unix_timestamp,
     )?;
 
+    if let Some(allowlist_entry) = allowlist_entry {
+        allowlist_entry.validate_net_deposits(vault_depositor.net_deposits)?;
+    }
+
     drop(vault);
     drop(user);
     drop(vp);
@@ -106,6 +127,8 @@ pub struct Deposit<'info> {
     pub user_token_account: Box<Account<'info, TokenAccount>>,
     pub drift_program: Program<'info, Drift>,
     pub token_program: Program<'info, Token>,
+    /// required if the vault is permissioned
+    pub allowlist_entry: Option<AccountLoader<'info, AllowlistEntry>>,
 }
 
 impl<'info> TokenTransferCPI for Context<'_, '_, '_, 'info, Deposit<'info>> {
@@ -50,11 +50,46 @@ pub fn initialize_vault<'info>(
     vault.profit_share = params.profit_share;
 
     validate!(
-        params.hurdle_rate == 0,
+        params.hurdle_rate < PERCENTAGE_PRECISION_U64.cast()?,
         ErrorCode::InvalidVaultInitialization,
-        "hurdle rate not implemented"
+        "hurdle rate must be < 100%"
     )?;
     vault.hurdle_rate = params.hurdle_rate;
+
+    validate!(
+        params.crystallization_period >= 0 && params.crystallization_period <= ONE_DAY * 365,
+        ErrorCode::InvalidVaultInitialization,
+        "crystallization period must be between 0 and 1 year"
+    )?;
+    vault.crystallization_period = params.crystallization_period;
+
+    validate!(
+        params.deposit_fee < PERCENTAGE_PRECISION_U64.cast()?,
+        ErrorCode::InvalidVaultInitialization,
+        "deposit fee must be < 100%"
+    )?;
+    vault.deposit_fee = params.deposit_fee;
+
+    validate!(
+        params.withdraw_fee < PERCENTAGE_PRECISION_U64.cast()?,
+        ErrorCode::InvalidVaultInitialization,
+        "withdraw fee must be < 100%"
+    )?;
+    vault.withdraw_fee = params.withdraw_fee;
+
+    validate!(
+        params.min_lockup_period >= 0 && params.min_lockup_period <= ONE_DAY * 365,
+        ErrorCode::InvalidVaultInitialization,
+        "min lockup period must be between 0 and 1 year"
+    )?;
+    vault.min_lockup_period = params.min_lockup_period;
+
+    validate!(
+        params.early_withdraw_penalty < PERCENTAGE_PRECISION_U64.cast()?,
+        ErrorCode::InvalidVaultInitialization,
+        "early withdraw penalty must be < 100%"
+    )?;
+    vault.early_withdraw_penalty = params.early_withdraw_penalty;
     vault.bump = bump;
     vault.permissioned = params.permissioned;
 
@@ -77,6 +112,11 @@ pub struct VaultParams {
     pub hurdle_rate: u32,
     pub spot_market_index: u16,
     pub permissioned: bool,
+    pub crystallization_period: i64,
+    pub deposit_fee: u32,
+    pub withdraw_fee: u32,
+    pub min_lockup_period: i64,
+    pub early_withdraw_penalty: u32,
 }
 
 #[derive(Accounts)]
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::error::ErrorCode;
-use crate::state::Vault;
+use crate::state::{AllowlistEntry, Vault};
 use crate::{validate, Size, VaultDepositor};
 
 pub fn initialize_vault_depositor(ctx: Context<InitializeVaultDepositor>) -> Result<()> {
@@ -12,19 +12,30 @@ pub fn initialize_vault_depositor(ctx: Context<InitializeVaultDepositor>) -> Res
 
     let vault = ctx.accounts.vault.load()?;
     if vault.permissioned {
-        validate!(
-            vault.manager == *ctx.accounts.payer.key,
-            ErrorCode::PermissionedVault,
-            "Vault depositor can only be created by vault manager"
-        )?;
-    } else {
-        validate!(
-            vault_depositor.authority == *ctx.accounts.payer.key,
-            ErrorCode::Default,
-            "Vault depositor authority must pay to create account"
+        let allowlist_entry = ctx
+            .accounts
+            .allowlist_entry
+            .as_ref()
+            .ok_or(ErrorCode::NotAllowlisted)?
+            .load()?;
+        allowlist_entry.validate(
+            &ctx.accounts.vault.key(),
+            &vault_depositor.authority,
+            Clock::get()?.unix_timestamp,
         )?;
     }
 
+    // a program-derived authority (e.g. a PDA holding data) can't pay for the account,
+    // so signing as the authority is accepted in place of paying. the manager can still
+    // create vault depositors for allowlisted authorities of a permissioned vault
+    validate!(
+        vault_depositor.authority == *ctx.accounts.payer.key
+            || ctx.accounts.authority.is_signer
+            || (vault.permissioned && vault.manager == *ctx.accounts.payer.key),
+        ErrorCode::Default,
+        "Vault depositor authority must pay to create account or sign"
+    )?;
+
     Ok(())
 }
 
@@ -39,10 +50,12 @@ pub struct InitializeVaultDepositor<'info> {
       payer = payer
     )]
     pub vault_depositor: AccountLoader<'info, VaultDepositor>,
-    /// CHECK: dont need to sign if vault is permissioned
+    /// CHECK: dont need to sign if the manager of a permissioned vault or the authority is the payer
     pub authority: AccountInfo<'info>,
     #[account(mut)]
     pub payer: Signer<'info>,
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
+    /// required if the vault is permissioned
+    pub allowlist_entry: Option<AccountLoader<'info, AllowlistEntry>>,
 }
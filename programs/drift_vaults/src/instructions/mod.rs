@@ -1,9 +1,11 @@
 pub use add_insurance_fund_stake::*;
+pub use add_to_allowlist::*;
 pub use apply_profit_share::*;
 pub use apply_rebase::*;
 pub use apply_rebase_tokenized_depositor::*;
 pub use cancel_request_remove_insurance_fund_stake::*;
 pub use cancel_withdraw_request::*;
+pub use crystallize_fees::*;
 pub use deposit::*;
 pub use force_withdraw::*;
 pub use initialize_insurance_fund_stake::*;
@@ -20,6 +22,7 @@ pub use protocol_cancel_withdraw_request::*;
 pub use protocol_request_withdraw::*;
 pub use protocol_withdraw::*;
 pub use redeem_tokens::*;
+pub use remove_from_allowlist::*;
 pub use remove_insurance_fund_stake::*;
 pub use request_remove_insurance_fund_stake::*;
 pub use request_withdraw::*;
@@ -32,12 +35,14 @@ pub use update_vault_protocol::*;
 pub use withdraw::*;
 
 mod add_insurance_fund_stake;
+mod add_to_allowlist;
 mod apply_profit_share;
 mod apply_rebase;
 mod apply_rebase_tokenized_depositor;
 mod cancel_request_remove_insurance_fund_stake;
 mod cancel_withdraw_request;
 pub mod constraints;
+mod crystallize_fees;
 mod deposit;
 mod force_withdraw;
 mod initialize_insurance_fund_stake;
@@ -54,6 +59,7 @@ mod protocol_cancel_withdraw_request;
 mod protocol_request_withdraw;
 mod protocol_withdraw;
 mod redeem_tokens;
+mod remove_from_allowlist;
 mod remove_insurance_fund_stake;
 mod request_remove_insurance_fund_stake;
 mod request_withdraw;
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::constraints::is_manager_for_vault;
+use crate::state::{AllowlistEntry, Vault};
+
+pub fn remove_from_allowlist(_ctx: Context<RemoveFromAllowlist>) -> Result<()> {
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveFromAllowlist<'info> {
+    #[account(
+        constraint = is_manager_for_vault(&vault, &manager)?,
+    )]
+    pub vault: AccountLoader<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"allowlist_entry", vault.key().as_ref(), authority.key().as_ref()],
+        bump,
+        close = manager
+    )]
+    pub allowlist_entry: AccountLoader<'info, AllowlistEntry>,
+    /// CHECK: the vault depositor authority being removed from the allowlist
+    pub authority: AccountInfo<'info>,
+    #[account(mut)]
+    pub manager: Signer<'info>,
+}
@@ -63,6 +63,8 @@ pub fn tokenize_shares<'info>(
         "Cannot tokenize shares with a pending withdraw request"
     )?;
 
+    vault_depositor.validate_tokenize_shares(&vault, clock.unix_timestamp)?;
+
     let total_supply_before = ctx.accounts.mint.supply;
 
     let (shares_transferred, _) = vault_depositor.transfer_shares(
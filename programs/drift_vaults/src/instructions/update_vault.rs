@@ -1,3 +1,4 @@
+use crate::constants::ONE_DAY;
 use crate::constraints::is_manager_for_vault;
 use crate::{error::ErrorCode, validate, Vault};
 use anchor_lang::prelude::*;
@@ -54,6 +55,52 @@ pub fn update_vault<'info>(
         vault.hurdle_rate = hurdle_rate;
     }
 
+    if let Some(crystallization_period) = params.crystallization_period {
+        validate!(
+            crystallization_period > vault.crystallization_period
+                && crystallization_period <= ONE_DAY * 365,
+            ErrorCode::InvalidVaultUpdate,
+            "new crystallization period must be longer than existing crystallization period and at most 1 year"
+        )?;
+        vault.crystallization_period = crystallization_period;
+    }
+
+    if let Some(deposit_fee) = params.deposit_fee {
+        validate!(
+            deposit_fee < vault.deposit_fee,
+            ErrorCode::InvalidVaultUpdate,
+            "new deposit fee must be less than existing deposit fee"
+        )?;
+        vault.deposit_fee = deposit_fee;
+    }
+
+    if let Some(withdraw_fee) = params.withdraw_fee {
+        validate!(
+            withdraw_fee < vault.withdraw_fee,
+            ErrorCode::InvalidVaultUpdate,
+            "new withdraw fee must be less than existing withdraw fee"
+        )?;
+        vault.withdraw_fee = withdraw_fee;
+    }
+
+    if let Some(min_lockup_period) = params.min_lockup_period {
+        validate!(
+            min_lockup_period < vault.min_lockup_period,
+            ErrorCode::InvalidVaultUpdate,
+            "new min lockup period must be shorter than existing min lockup period"
+        )?;
+        vault.min_lockup_period = min_lockup_period;
+    }
+
+    if let Some(early_withdraw_penalty) = params.early_withdraw_penalty {
+        validate!(
+            early_withdraw_penalty < vault.early_withdraw_penalty,
+            ErrorCode::InvalidVaultUpdate,
+            "new early withdraw penalty must be less than existing early withdraw penalty"
+        )?;
+        vault.early_withdraw_penalty = early_withdraw_penalty;
+    }
+
     if let Some(permissioned) = params.permissioned {
         vault.permissioned = permissioned;
     }
@@ -72,6 +119,11 @@ pub struct UpdateVaultParams {
     pub profit_share: Option<u32>,
     pub hurdle_rate: Option<u32>,
     pub permissioned: Option<bool>,
+    pub crystallization_period: Option<i64>,
+    pub deposit_fee: Option<u32>,
+    pub withdraw_fee: Option<u32>,
+    pub min_lockup_period: Option<i64>,
+    pub early_withdraw_penalty: Option<u32>,
 }
 
 #[derive(Accounts)]
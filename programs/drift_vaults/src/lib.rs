@@ -11,6 +11,12 @@ pub mod state;
 mod tests;
 mod token_cpi;
 
+// instruction params are exported for off-chain clients building instructions
+pub use instructions::{
+    AllowlistEntryParams, InitializeTokenizedVaultDepositorParams, UpdateVaultParams,
+    UpdateVaultProtocolParams, VaultParams, VaultProtocolParams, VaultWithProtocolParams,
+};
+
 declare_id!("vAuLTsyrvSfZRuRB3XgvkPwNGgYSs9YRYymVebLKoxR");
 
 #[program]
@@ -63,6 +69,17 @@ pub mod drift_vaults {
         instructions::initialize_vault_depositor(ctx)
     }
 
+    pub fn add_to_allowlist(
+        ctx: Context<AddToAllowlist>,
+        params: AllowlistEntryParams,
+    ) -> Result<()> {
+        instructions::add_to_allowlist(ctx, params)
+    }
+
+    pub fn remove_from_allowlist(ctx: Context<RemoveFromAllowlist>) -> Result<()> {
+        instructions::remove_from_allowlist(ctx)
+    }
+
     pub fn initialize_tokenized_vault_depositor(
         ctx: Context<InitializeTokenizedVaultDepositor>,
         params: InitializeTokenizedVaultDepositorParams,
@@ -157,6 +174,12 @@ pub mod drift_vaults {
         instructions::apply_profit_share(ctx)
     }
 
+    pub fn crystallize_fees<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CrystallizeFees<'info>>,
+    ) -> Result<()> {
+        instructions::crystallize_fees(ctx)
+    }
+
     pub fn apply_rebase<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, ApplyRebase<'info>>,
     ) -> Result<()> {
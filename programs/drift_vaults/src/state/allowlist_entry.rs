@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use drift::math::casting::Cast;
+use drift_macros::assert_no_slop;
+use static_assertions::const_assert_eq;
+
+use crate::error::ErrorCode;
+use crate::{validate, Size};
+
+#[assert_no_slop]
+#[account(zero_copy(unsafe))]
+#[derive(Default, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub struct AllowlistEntry {
+    /// The permissioned vault the entry belongs to
+    pub vault: Pubkey,
+    /// The vault depositor authority allowed to deposit into the vault. It is a pda of vault and authority
+    pub authority: Pubkey,
+    /// The max net deposits of the authority's vault depositor, 0 means no cap
+    pub max_deposit_amount: u64,
+    /// The ts after which the entry is no longer valid, 0 means the entry never expires
+    pub expiry_ts: i64,
+    /// The bump for the allowlist entry pda
+    pub bump: u8,
+    pub padding1: [u8; 7],
+    pub padding: [u64; 4],
+}
+
+impl Size for AllowlistEntry {
+    const SIZE: usize = 120 + 8;
+}
+
+const_assert_eq!(
+    AllowlistEntry::SIZE,
+    std::mem::size_of::<AllowlistEntry>() + 8
+);
+
+impl AllowlistEntry {
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expiry_ts != 0 && now >= self.expiry_ts
+    }
+
+    pub fn validate(&self, vault: &Pubkey, authority: &Pubkey, now: i64) -> Result<()> {
+        validate!(
+            self.vault == *vault && self.authority == *authority,
+            ErrorCode::NotAllowlisted,
+            "allowlist entry is for vault {} and authority {}",
+            self.vault,
+            self.authority
+        )?;
+
+        validate!(
+            !self.is_expired(now),
+            ErrorCode::AllowlistEntryExpired,
+            "allowlist entry expired at {}",
+            self.expiry_ts
+        )?;
+
+        Ok(())
+    }
+
+    pub fn validate_net_deposits(&self, net_deposits: i64) -> Result<()> {
+        if self.max_deposit_amount == 0 {
+            return Ok(());
+        }
+
+        let net_deposits = net_deposits.max(0).cast::<u64>()?;
+        validate!(
+            net_deposits <= self.max_deposit_amount,
+            ErrorCode::AllowlistDepositLimitExceeded,
+            "net deposits {} exceed allowlist max deposit amount {}",
+            net_deposits,
+            self.max_deposit_amount
+        )?;
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 pub use account_maps::*;
+pub use allowlist_entry::*;
 pub use tokenized_vault_depositor::*;
 pub use traits::*;
 pub use vault::*;
@@ -7,6 +8,7 @@ pub use vault_protocol::*;
 pub use withdraw_unit::*;
 
 pub mod account_maps;
+pub mod allowlist_entry;
 pub mod events;
 pub mod tokenized_vault_depositor;
 pub mod traits;
@@ -51,7 +51,13 @@ pub struct TokenizedVaultDepositor {
     /// The bump for the vault pda
     pub bump: u8,
     pub padding1: [u8; 3],
-    pub padding: [u64; 10],
+    /// the last ts the hurdle was accrued
+    pub last_hurdle_ts: i64,
+    /// the token amount of hurdle accrued since the last high-water mark, profit share is only charged on gains above it
+    pub hurdle_amount: u64,
+    /// start ts of the current crystallization period
+    pub crystallization_period_start: i64,
+    pub padding: [u64; 7],
 }
 
 impl Size for TokenizedVaultDepositor {
@@ -105,6 +111,27 @@ impl VaultDepositorBase for TokenizedVaultDepositor {
     fn set_profit_share_fee_paid(&mut self, amount: u64) {
         self.profit_share_fee_paid = amount;
     }
+
+    fn get_last_hurdle_ts(&self) -> i64 {
+        self.last_hurdle_ts
+    }
+    fn set_last_hurdle_ts(&mut self, ts: i64) {
+        self.last_hurdle_ts = ts;
+    }
+
+    fn get_hurdle_amount(&self) -> u64 {
+        self.hurdle_amount
+    }
+    fn set_hurdle_amount(&mut self, amount: u64) {
+        self.hurdle_amount = amount;
+    }
+
+    fn get_crystallization_period_start(&self) -> i64 {
+        self.crystallization_period_start
+    }
+    fn set_crystallization_period_start(&mut self, ts: i64) {
+        self.crystallization_period_start = ts;
+    }
 }
 
 impl TokenizedVaultDepositor {
@@ -131,7 +158,10 @@ impl TokenizedVaultDepositor {
             vault_shares_base,
             bump,
             padding1: [0; 3],
-            padding: [0; 10],
+            last_hurdle_ts: now,
+            hurdle_amount: 0,
+            crystallization_period_start: now,
+            padding: [0; 7],
         }
     }
 
@@ -172,7 +202,7 @@ impl TokenizedVaultDepositor {
             protocol_fee_shares,
         } = vault.apply_fee(vault_protocol, vault_equity, now)?;
         let (manager_profit_share, protocol_profit_share) =
-            self.apply_profit_share(vault_equity, vault, vault_protocol)?;
+            self.crystallize_profit_share(vault_equity, vault, vault_protocol, now)?;
 
         let vault_shares_before = self.checked_vault_shares(vault)?;
         let total_vault_shares_before = vault.total_shares;
@@ -226,6 +256,8 @@ impl TokenizedVaultDepositor {
                         .cast()?,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee: 0,
                 });
             }
             Some(_) => {
@@ -249,6 +281,8 @@ impl TokenizedVaultDepositor {
                     manager_profit_share,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee: 0,
                     protocol_shares_before,
                     protocol_shares_after: vault.get_protocol_shares(vault_protocol),
                 });
@@ -276,7 +310,7 @@ impl TokenizedVaultDepositor {
             protocol_fee_shares,
         } = vault.apply_fee(vault_protocol, vault_equity, now)?;
         let (manager_profit_share, protocol_profit_share) =
-            self.apply_profit_share(vault_equity, vault, vault_protocol)?;
+            self.apply_profit_share(vault_equity, vault, vault_protocol, now)?;
 
         let vault_shares_before = self.checked_vault_shares(vault)?;
         let total_vault_shares_before = vault.total_shares;
@@ -320,6 +354,8 @@ impl TokenizedVaultDepositor {
                         .cast()?,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee: 0,
                 });
             }
             Some(_) => {
@@ -343,6 +379,8 @@ impl TokenizedVaultDepositor {
                     manager_profit_share,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee: 0,
                     protocol_shares_before,
                     protocol_shares_after: vault.get_protocol_shares(vault_protocol),
                 });
@@ -565,7 +603,7 @@ mod tests {
 
         let tvd_shares_before = tvd.get_vault_shares();
         let (manager_profit_share, protocol_profit_share) = tvd
-            .apply_profit_share(vault_equity + profit, vault, &mut None)
+            .apply_profit_share(vault_equity + profit, vault, &mut None, now)
             .unwrap();
         let tvd_shares_after = tvd.get_vault_shares();
 
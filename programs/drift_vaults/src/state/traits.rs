@@ -9,7 +9,7 @@ use crate::{validate, VaultFee, VaultProtocol, WithdrawUnit};
 use anchor_lang::prelude::*;
 
 use drift::math::casting::Cast;
-use drift::math::constants::PERCENTAGE_PRECISION;
+use drift::math::constants::{ONE_YEAR, PERCENTAGE_PRECISION};
 use drift::math::insurance::{
     if_shares_to_vault_amount as depositor_shares_to_vault_amount,
     vault_amount_to_if_shares as vault_amount_to_depositor_shares,
@@ -39,6 +39,15 @@ pub trait VaultDepositorBase {
     fn get_profit_share_fee_paid(&self) -> u64;
     fn set_profit_share_fee_paid(&mut self, amount: u64);
 
+    fn get_last_hurdle_ts(&self) -> i64;
+    fn set_last_hurdle_ts(&mut self, ts: i64);
+
+    fn get_hurdle_amount(&self) -> u64;
+    fn set_hurdle_amount(&mut self, amount: u64);
+
+    fn get_crystallization_period_start(&self) -> i64;
+    fn set_crystallization_period_start(&mut self, ts: i64);
+
     fn validate_base(&self, vault: &Vault) -> Result<()> {
         validate!(
             self.get_vault_shares_base() == vault.shares_base,
@@ -78,25 +87,61 @@ pub trait VaultDepositorBase {
         Ok(())
     }
 
+    /// The token amount the depositor has to exceed before profit share is charged:
+    /// net deposits plus the gains profit share was already paid on
+    fn high_water_mark(&self) -> Result<i64> {
+        self.get_net_deposits()
+            .safe_add(self.get_cumulative_profit_share_amount())
+    }
+
+    /// Accrue the annualized hurdle on the high-water mark for the time elapsed since the last accrual
+    fn accrue_hurdle(&mut self, vault: &Vault, now: i64) -> Result<()> {
+        let last_hurdle_ts = self.get_last_hurdle_ts();
+        // depositors created before the hurdle was tracked start accruing from now
+        if last_hurdle_ts > 0 && now > last_hurdle_ts && vault.hurdle_rate > 0 {
+            let high_water_mark = self.high_water_mark()?;
+            if high_water_mark > 0 {
+                let hurdle_accrued: u64 = high_water_mark
+                    .cast::<u128>()?
+                    .safe_mul(vault.hurdle_rate.cast()?)?
+                    .safe_mul(now.safe_sub(last_hurdle_ts)?.cast()?)?
+                    .safe_div(PERCENTAGE_PRECISION.safe_mul(ONE_YEAR)?)?
+                    .cast()?;
+                self.set_hurdle_amount(self.get_hurdle_amount().safe_add(hurdle_accrued)?);
+            }
+        }
+
+        if now > last_hurdle_ts {
+            self.set_last_hurdle_ts(now);
+        }
+
+        Ok(())
+    }
+
     fn calculate_profit_share_and_update(
         &mut self,
         total_amount: u64,
         vault: &Vault,
         vault_protocol: &mut Option<RefMut<VaultProtocol>>,
+        now: i64,
     ) -> Result<(u128, u128)> {
-        let profit = total_amount.cast::<i64>()?.safe_sub(
-            self.get_net_deposits()
-                .safe_add(self.get_cumulative_profit_share_amount())?,
-        )?;
-        if profit > 0 {
+        self.accrue_hurdle(vault, now)?;
+
+        let profit = total_amount
+            .cast::<i64>()?
+            .safe_sub(self.high_water_mark()?)?;
+        let hurdle_amount = self.get_hurdle_amount().cast::<i64>()?;
+        // profit share is only charged on returns above the hurdle accrued since the last high-water mark
+        if profit > hurdle_amount {
             let profit_u128 = profit.cast::<u128>()?;
+            let profit_above_hurdle = profit.safe_sub(hurdle_amount)?.cast::<u128>()?;
 
-            let manager_profit_share_amount = profit_u128
+            let manager_profit_share_amount = profit_above_hurdle
                 .safe_mul(vault.profit_share.cast()?)?
                 .safe_div(PERCENTAGE_PRECISION)?;
             let protocol_profit_share_amount = match vault_protocol {
                 None => 0,
-                Some(vp) => profit_u128
+                Some(vp) => profit_above_hurdle
                     .safe_mul(vp.protocol_profit_share.cast()?)?
                     .safe_div(PERCENTAGE_PRECISION)?,
             };
@@ -107,6 +152,7 @@ pub trait VaultDepositorBase {
                 self.get_cumulative_profit_share_amount()
                     .safe_add(profit_u128.cast()?)?,
             );
+            self.set_hurdle_amount(0);
 
             self.set_profit_share_fee_paid(
                 self.get_profit_share_fee_paid()
@@ -119,11 +165,79 @@ pub trait VaultDepositorBase {
         Ok((0, 0))
     }
 
+    /// Whether the depositor's crystallization period has ended and profit share can be charged
+    fn crystallization_due(&self, vault: &Vault, now: i64) -> Result<bool> {
+        if vault.crystallization_period == 0 {
+            return Ok(true);
+        }
+
+        Ok(now
+            >= self
+                .get_crystallization_period_start()
+                .safe_add(vault.crystallization_period)?)
+    }
+
+    /// Validate that profit share can be crystallized by a keeper. Vaults without a crystallization period
+    /// already charge profit share whenever the depositor acts, so nobody else can pick the moment for them.
+    fn validate_crystallize_fees(&self, vault: &Vault, now: i64) -> Result<()> {
+        validate!(
+            vault.crystallization_period > 0,
+            ErrorCode::NoCrystallizationPeriod,
+            "vault has no crystallization period"
+        )?;
+
+        validate!(
+            self.crystallization_due(vault, now)?,
+            ErrorCode::CrystallizationPeriodNotEnded,
+            "crystallization period ends at {}",
+            self.get_crystallization_period_start()
+                .saturating_add(vault.crystallization_period)
+        )?;
+
+        Ok(())
+    }
+
+    /// Move the crystallization period start to the start of the period `now` falls in
+    fn update_crystallization_period(&mut self, vault: &Vault, now: i64) -> Result<()> {
+        if vault.crystallization_period == 0 || !self.crystallization_due(vault, now)? {
+            return Ok(());
+        }
+
+        let period_start = self.get_crystallization_period_start();
+        let periods_elapsed = now
+            .safe_sub(period_start)?
+            .safe_div(vault.crystallization_period)?;
+        self.set_crystallization_period_start(
+            period_start.safe_add(periods_elapsed.safe_mul(vault.crystallization_period)?)?,
+        );
+
+        Ok(())
+    }
+
+    /// Apply profit share only if the depositor's crystallization period has ended.
+    /// Within the period the hurdle keeps accruing and no profit share is charged.
+    fn crystallize_profit_share(
+        &mut self,
+        vault_equity: u64,
+        vault: &mut Vault,
+        vault_protocol: &mut Option<RefMut<VaultProtocol>>,
+        now: i64,
+    ) -> Result<(u64, u64)> {
+        if !self.crystallization_due(vault, now)? {
+            self.accrue_hurdle(vault, now)?;
+            return Ok((0, 0));
+        }
+
+        self.apply_profit_share(vault_equity, vault, vault_protocol, now)
+    }
+
+    /// Apply profit share regardless of the crystallization period, e.g. when shares leave the depositor
     fn apply_profit_share(
         &mut self,
         vault_equity: u64,
         vault: &mut Vault,
         vault_protocol: &mut Option<RefMut<VaultProtocol>>,
+        now: i64,
     ) -> Result<(u64, u64)> {
         let total_amount = depositor_shares_to_vault_amount(
             self.get_vault_shares(),
@@ -132,7 +246,8 @@ pub trait VaultDepositorBase {
         )?;
 
         let (manager_profit_share, protocol_profit_share) =
-            self.calculate_profit_share_and_update(total_amount, vault, vault_protocol)?;
+            self.calculate_profit_share_and_update(total_amount, vault, vault_protocol, now)?;
+        self.update_crystallization_period(vault, now)?;
         let manager_profit_share: u64 = manager_profit_share.cast()?;
         let protocol_profit_share: u64 = protocol_profit_share.cast()?;
         let profit_share = manager_profit_share
@@ -254,9 +369,9 @@ pub trait VaultDepositorBase {
         } = vault.apply_fee(vault_protocol, vault_equity, now)?;
 
         let (from_manager_profit_share, from_protocol_profit_share) =
-            self.apply_profit_share(vault_equity, vault, vault_protocol)?;
+            self.apply_profit_share(vault_equity, vault, vault_protocol, now)?;
         let (to_manager_profit_share, to_protocol_profit_share) =
-            to.apply_profit_share(vault_equity, vault, vault_protocol)?;
+            to.crystallize_profit_share(vault_equity, vault, vault_protocol, now)?;
 
         let (withdraw_value, n_shares) = withdraw_unit.get_withdraw_value_and_shares(
             withdraw_amount,
@@ -332,6 +447,8 @@ pub trait VaultDepositorBase {
                         .cast()?,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee: 0,
                 });
 
                 emit!(VaultDepositorRecord {
@@ -353,6 +470,8 @@ pub trait VaultDepositorBase {
                         .cast()?,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee: 0,
                 });
             }
             Some(_) => {
@@ -376,6 +495,8 @@ pub trait VaultDepositorBase {
                     manager_profit_share: from_manager_profit_share,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee: 0,
                     protocol_shares_before,
                     protocol_shares_after: vault.get_protocol_shares(vault_protocol),
                 });
@@ -400,6 +521,8 @@ pub trait VaultDepositorBase {
                     manager_profit_share: from_manager_profit_share,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee: 0,
                     protocol_shares_before,
                     protocol_shares_after: vault.get_protocol_shares(vault_protocol),
                 });
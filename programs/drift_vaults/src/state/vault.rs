@@ -102,7 +102,21 @@ pub struct Vault {
     /// The optional [`VaultProtocol`] account.
     pub vault_protocol: bool,
     pub padding1: [u8; 7],
-    pub padding: [u64; 7],
+    /// The period (in seconds) at which profit share crystallizes for each vault depositor.
+    /// If 0, profit share is charged whenever it is applied.
+    pub crystallization_period: i64,
+    /// Percentage the manager charges on deposits, paid in shares of the deposit: PERCENTAGE_PRECISION
+    pub deposit_fee: u32,
+    /// Percentage the manager charges on withdrawals, paid in shares of the withdrawal: PERCENTAGE_PRECISION
+    pub withdraw_fee: u32,
+    /// The period (in seconds) after a vault depositor's last deposit during which withdrawals pay a penalty.
+    /// If 0, there is no lockup.
+    pub min_lockup_period: i64,
+    /// Penalty on withdrawals requested right after a deposit, declining linearly to 0 at the end of the lockup.
+    /// The penalty stays in the vault for the remaining depositors: PERCENTAGE_PRECISION
+    pub early_withdraw_penalty: u32,
+    pub padding2: u32,
+    pub padding: [u64; 3],
 }
 
 impl Vault {
@@ -738,6 +752,28 @@ impl Vault {
         Ok(n_tokens)
     }
 
+    /// The penalty rate for a withdraw requested at `request_ts` by a depositor who last deposited at `last_deposit_ts`: PERCENTAGE_PRECISION
+    pub fn early_withdraw_penalty_rate(
+        &self,
+        last_deposit_ts: i64,
+        request_ts: i64,
+    ) -> Result<u128> {
+        if self.min_lockup_period == 0 || self.early_withdraw_penalty == 0 {
+            return Ok(0);
+        }
+
+        let time_since_deposit = request_ts.safe_sub(last_deposit_ts)?.max(0);
+        if time_since_deposit >= self.min_lockup_period {
+            return Ok(0);
+        }
+
+        let lockup_remaining = self.min_lockup_period.safe_sub(time_since_deposit)?;
+        self.early_withdraw_penalty
+            .cast::<u128>()?
+            .safe_mul(lockup_remaining.cast()?)?
+            .safe_div(self.min_lockup_period.cast()?)
+    }
+
     pub fn in_liquidation(&self) -> bool {
         self.liquidation_delegate != Pubkey::default()
     }
@@ -1121,6 +1157,8 @@ impl Vault {
                     profit_share: params.manager_profit_share,
                     management_fee: params.management_fee,
                     management_fee_shares: params.management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee: 0,
                 });
             }
             Some(protocol_params) => {
@@ -1141,6 +1179,8 @@ impl Vault {
                     manager_profit_share: params.manager_profit_share,
                     management_fee: params.management_fee,
                     management_fee_shares: params.management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee: 0,
 
                     protocol_profit_share: protocol_params.protocol_profit_share,
                     protocol_fee: protocol_params.protocol_fee,
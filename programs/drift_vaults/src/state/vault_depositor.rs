@@ -57,7 +57,15 @@ pub struct VaultDepositor {
     /// the exponent for vault_shares decimal places
     pub vault_shares_base: u32,
     pub padding1: u32,
-    pub padding: [u64; 8],
+    /// the last ts the hurdle was accrued
+    pub last_hurdle_ts: i64,
+    /// the token amount of hurdle accrued since the last high-water mark, profit share is only charged on gains above it
+    pub hurdle_amount: u64,
+    /// start ts of the current crystallization period
+    pub crystallization_period_start: i64,
+    /// ts of the last deposit, withdrawals requested within the vault's lockup period after it pay a penalty
+    pub last_deposit_ts: i64,
+    pub padding: [u64; 4],
 }
 
 impl Size for VaultDepositor {
@@ -111,6 +119,27 @@ impl VaultDepositorBase for VaultDepositor {
     fn set_profit_share_fee_paid(&mut self, amount: u64) {
         self.profit_share_fee_paid = amount;
     }
+
+    fn get_last_hurdle_ts(&self) -> i64 {
+        self.last_hurdle_ts
+    }
+    fn set_last_hurdle_ts(&mut self, ts: i64) {
+        self.last_hurdle_ts = ts;
+    }
+
+    fn get_hurdle_amount(&self) -> u64 {
+        self.hurdle_amount
+    }
+    fn set_hurdle_amount(&mut self, amount: u64) {
+        self.hurdle_amount = amount;
+    }
+
+    fn get_crystallization_period_start(&self) -> i64 {
+        self.crystallization_period_start
+    }
+    fn set_crystallization_period_start(&mut self, ts: i64) {
+        self.crystallization_period_start = ts;
+    }
 }
 
 impl VaultDepositor {
@@ -129,7 +158,11 @@ impl VaultDepositor {
             cumulative_profit_share_amount: 0,
             padding1: 0,
             profit_share_fee_paid: 0,
-            padding: [0u64; 8],
+            last_hurdle_ts: now,
+            hurdle_amount: 0,
+            crystallization_period_start: now,
+            last_deposit_ts: 0,
+            padding: [0u64; 4],
         }
     }
 
@@ -189,42 +222,6 @@ impl VaultDepositor {
         }
     }
 
-    pub fn calculate_profit_share_and_update(
-        &mut self,
-        total_amount: u64,
-        vault: &Vault,
-        vault_protocol: &mut Option<RefMut<VaultProtocol>>,
-    ) -> Result<(u128, u128)> {
-        let profit = total_amount.cast::<i64>()?.safe_sub(
-            self.net_deposits
-                .safe_add(self.cumulative_profit_share_amount)?,
-        )?;
-        if profit > 0 {
-            let profit_u128 = profit.cast::<u128>()?;
-
-            let manager_profit_share_amount = profit_u128
-                .safe_mul(vault.profit_share.cast()?)?
-                .safe_div(PERCENTAGE_PRECISION)?;
-            let protocol_profit_share_amount = match vault_protocol {
-                None => 0,
-                Some(vp) => profit_u128
-                    .safe_mul(vp.protocol_profit_share.cast()?)?
-                    .safe_div(PERCENTAGE_PRECISION)?,
-            };
-            let profit_share_amount =
-                manager_profit_share_amount.safe_add(protocol_profit_share_amount)?;
-            self.cumulative_profit_share_amount = self
-                .cumulative_profit_share_amount
-                .safe_add(profit_u128.cast()?)?;
-            self.profit_share_fee_paid = self
-                .profit_share_fee_paid
-                .safe_add(profit_share_amount.cast()?)?;
-            return Ok((manager_profit_share_amount, protocol_profit_share_amount));
-        }
-
-        Ok((0, 0))
-    }
-
     pub fn deposit(
         &mut self,
         amount: u64,
@@ -275,20 +272,32 @@ impl VaultDepositor {
             protocol_fee_shares,
         } = vault.apply_fee(vault_protocol, vault_equity, now)?;
         let (manager_profit_share, protocol_profit_share) =
-            self.apply_profit_share(vault_equity, vault, vault_protocol)?;
+            self.crystallize_profit_share(vault_equity, vault, vault_protocol, now)?;
 
         let n_shares = vault_amount_to_depositor_shares(amount, vault.total_shares, vault_equity)?;
 
+        // the deposit fee is paid to the manager as shares of the deposit
+        let deposit_fee: u64 = amount
+            .cast::<u128>()?
+            .safe_mul(vault.deposit_fee.cast()?)?
+            .safe_div(PERCENTAGE_PRECISION)?
+            .cast()?;
+        let deposit_fee_shares = n_shares
+            .safe_mul(vault.deposit_fee.cast()?)?
+            .safe_div(PERCENTAGE_PRECISION)?;
+        let depositor_shares = n_shares.safe_sub(deposit_fee_shares)?;
+
         self.total_deposits = self.total_deposits.saturating_add(amount);
         self.net_deposits = self.net_deposits.safe_add(amount.cast()?)?;
+        self.last_deposit_ts = now;
 
         vault.total_deposits = vault.total_deposits.saturating_add(amount);
         vault.net_deposits = vault.net_deposits.safe_add(amount.cast()?)?;
 
-        self.increase_vault_shares(n_shares, vault)?;
+        self.increase_vault_shares(depositor_shares, vault)?;
 
         vault.total_shares = vault.total_shares.safe_add(n_shares)?;
-        vault.user_shares = vault.user_shares.safe_add(n_shares)?;
+        vault.user_shares = vault.user_shares.safe_add(depositor_shares)?;
 
         let vault_shares_after = self.checked_vault_shares(vault)?;
         let protocol_shares_after = vault.get_protocol_shares(vault_protocol);
@@ -312,6 +321,8 @@ impl VaultDepositor {
                     profit_share: manager_profit_share,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee,
+                    withdraw_fee: 0,
                 });
             }
             Some(_) => {
@@ -335,6 +346,8 @@ impl VaultDepositor {
                     manager_profit_share,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee,
+                    withdraw_fee: 0,
                     protocol_shares_before,
                     protocol_shares_after,
                 });
@@ -344,6 +357,19 @@ impl VaultDepositor {
         Ok(())
     }
 
+    /// Shares still within the lockup period can't be tokenized, otherwise they could be redeemed
+    /// into a vault depositor whose last deposit is older and withdrawn without the early withdraw penalty
+    pub fn validate_tokenize_shares(&self, vault: &Vault, now: i64) -> Result<()> {
+        validate!(
+            vault.early_withdraw_penalty_rate(self.last_deposit_ts, now)? == 0,
+            ErrorCode::InvalidTokenization,
+            "Cannot tokenize shares within the lockup period, last deposit ts: {}",
+            self.last_deposit_ts
+        )?;
+
+        Ok(())
+    }
+
     pub fn request_withdraw(
         &mut self,
         withdraw_amount: u64,
@@ -361,7 +387,7 @@ impl VaultDepositor {
             protocol_fee_shares,
         } = vault.apply_fee(vault_protocol, vault_equity, now)?;
         let (manager_profit_share, protocol_profit_share) =
-            self.apply_profit_share(vault_equity, vault, vault_protocol)?;
+            self.apply_profit_share(vault_equity, vault, vault_protocol, now)?;
 
         let (withdraw_value, n_shares) = withdraw_unit.get_withdraw_value_and_shares(
             withdraw_amount,
@@ -413,6 +439,8 @@ impl VaultDepositor {
                     profit_share: manager_profit_share,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee: 0,
                 });
             }
             Some(_) => {
@@ -436,6 +464,8 @@ impl VaultDepositor {
                     manager_profit_share,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee: 0,
                     protocol_shares_before,
                     protocol_shares_after,
                 });
@@ -502,6 +532,8 @@ impl VaultDepositor {
                     profit_share: 0,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee: 0,
                 });
             }
             Some(_) => {
@@ -525,6 +557,8 @@ impl VaultDepositor {
                     manager_profit_share: 0,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee: 0,
                     protocol_shares_before,
                     protocol_shares_after,
                 });
@@ -582,6 +616,30 @@ impl VaultDepositor {
             depositor_shares_to_vault_amount(n_shares, vault.total_shares, vault_equity)?;
 
         let withdraw_amount = amount.min(self.last_withdraw_request.value);
+
+        // the withdraw fee is paid to the manager as shares of the withdrawal
+        let withdraw_fee: u64 = withdraw_amount
+            .cast::<u128>()?
+            .safe_mul(vault.withdraw_fee.cast()?)?
+            .safe_div(PERCENTAGE_PRECISION)?
+            .cast()?;
+        let withdraw_fee_shares =
+            vault_amount_to_depositor_shares(withdraw_fee, vault.total_shares, vault_equity)?
+                .min(n_shares);
+
+        // the penalty for withdrawing within the lockup period is not paid out, it stays in the vault
+        let early_withdraw_penalty: u64 = withdraw_amount
+            .cast::<u128>()?
+            .safe_mul(vault.early_withdraw_penalty_rate(
+                self.last_deposit_ts,
+                self.last_withdraw_request.ts,
+            )?)?
+            .safe_div(PERCENTAGE_PRECISION)?
+            .cast()?;
+
+        let withdraw_amount = withdraw_amount
+            .safe_sub(withdraw_fee)?
+            .safe_sub(early_withdraw_penalty)?;
         msg!(
             "amount={}, last_withdraw_request_value={}",
             amount,
@@ -595,12 +653,17 @@ impl VaultDepositor {
 
         self.decrease_vault_shares(n_shares, vault)?;
 
+        // accrue the hurdle on the high-water mark before the withdraw lowers it
+        self.accrue_hurdle(vault, now)?;
+
         self.total_withdraws = self.total_withdraws.saturating_add(withdraw_amount);
         self.net_deposits = self.net_deposits.safe_sub(withdraw_amount.cast()?)?;
 
         vault.total_withdraws = vault.total_withdraws.saturating_add(withdraw_amount);
         vault.net_deposits = vault.net_deposits.safe_sub(withdraw_amount.cast()?)?;
-        vault.total_shares = vault.total_shares.safe_sub(n_shares)?;
+        vault.total_shares = vault
+            .total_shares
+            .safe_sub(n_shares.safe_sub(withdraw_fee_shares)?)?;
         vault.user_shares = vault.user_shares.safe_sub(n_shares)?;
         vault.total_withdraw_requested = vault
             .total_withdraw_requested
@@ -630,6 +693,8 @@ impl VaultDepositor {
                     profit_share: 0,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee,
                 });
             }
             Some(_) => {
@@ -653,12 +718,68 @@ impl VaultDepositor {
                     manager_profit_share: 0,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee,
                     protocol_shares_before,
                     protocol_shares_after,
                 });
             }
         }
 
+        if early_withdraw_penalty > 0 {
+            match vault_protocol {
+                None => {
+                    emit!(VaultDepositorRecord {
+                        ts: now,
+                        vault: vault.pubkey,
+                        depositor_authority: self.authority,
+                        action: VaultDepositorAction::Penalty,
+                        amount: early_withdraw_penalty,
+                        spot_market_index: vault.spot_market_index,
+                        vault_equity_before: vault_equity,
+                        vault_shares_before,
+                        user_vault_shares_before,
+                        total_vault_shares_before,
+                        vault_shares_after,
+                        total_vault_shares_after: vault.total_shares,
+                        user_vault_shares_after: vault.user_shares,
+                        profit_share: 0,
+                        management_fee: 0,
+                        management_fee_shares: 0,
+                        deposit_fee: 0,
+                        withdraw_fee: 0,
+                    });
+                }
+                Some(_) => {
+                    emit!(VaultDepositorV1Record {
+                        ts: now,
+                        vault: vault.pubkey,
+                        depositor_authority: self.authority,
+                        action: VaultDepositorAction::Penalty,
+                        amount: early_withdraw_penalty,
+                        spot_market_index: vault.spot_market_index,
+                        vault_equity_before: vault_equity,
+                        vault_shares_before,
+                        user_vault_shares_before,
+                        total_vault_shares_before,
+                        vault_shares_after,
+                        total_vault_shares_after: vault.total_shares,
+                        user_vault_shares_after: vault.user_shares,
+                        protocol_profit_share: 0,
+                        protocol_fee: 0,
+                        protocol_fee_shares: 0,
+                        manager_profit_share: 0,
+                        management_fee: 0,
+                        management_fee_shares: 0,
+                        deposit_fee: 0,
+                        withdraw_fee: 0,
+                        protocol_shares_before,
+                        protocol_shares_after,
+                    });
+                }
+            }
+        }
+
         let finishing_liquidation = vault.liquidation_delegate == self.authority;
 
         Ok((withdraw_amount, finishing_liquidation))
@@ -669,13 +790,29 @@ impl VaultDepositor {
         vault_equity: u64,
         vault: &mut Vault,
         vault_protocol: &mut Option<RefMut<VaultProtocol>>,
+        now: i64,
+    ) -> Result<(u64, u64)> {
+        validate!(
+            !self.last_withdraw_request.pending(),
+            ErrorCode::InvalidVaultDeposit,
+            "Cannot apply profit share to depositor with pending withdraw request"
+        )?;
+        VaultDepositorBase::apply_profit_share(self, vault_equity, vault, vault_protocol, now)
+    }
+
+    pub fn crystallize_profit_share(
+        &mut self,
+        vault_equity: u64,
+        vault: &mut Vault,
+        vault_protocol: &mut Option<RefMut<VaultProtocol>>,
+        now: i64,
     ) -> Result<(u64, u64)> {
         validate!(
             !self.last_withdraw_request.pending(),
             ErrorCode::InvalidVaultDeposit,
             "Cannot apply profit share to depositor with pending withdraw request"
         )?;
-        VaultDepositorBase::apply_profit_share(self, vault_equity, vault, vault_protocol)
+        VaultDepositorBase::crystallize_profit_share(self, vault_equity, vault, vault_protocol, now)
     }
 
     pub fn realize_profits(
@@ -698,7 +835,7 @@ impl VaultDepositor {
         let protocol_shares_before = vault.get_protocol_shares(vault_protocol);
 
         let (manager_profit_share, protocol_profit_share) =
-            self.apply_profit_share(vault_equity, vault, vault_protocol)?;
+            self.apply_profit_share(vault_equity, vault, vault_protocol, now)?;
         let profit_share = manager_profit_share.saturating_add(protocol_profit_share);
         let protocol_shares_after = vault.get_protocol_shares(vault_protocol);
 
@@ -721,6 +858,8 @@ impl VaultDepositor {
                     profit_share: manager_profit_share,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee: 0,
                 });
             }
             Some(_) => {
@@ -744,6 +883,8 @@ impl VaultDepositor {
                     manager_profit_share,
                     management_fee: management_fee_payment,
                     management_fee_shares,
+                    deposit_fee: 0,
+                    withdraw_fee: 0,
                     protocol_shares_before,
                     protocol_shares_after,
                 });
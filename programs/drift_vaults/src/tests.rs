@@ -338,7 +338,7 @@ mod vault_fcn {
 
         now += 60 * 60 * 24; // 1 day later
 
-        vd.apply_profit_share(vault_equity, &mut vault, &mut vp)
+        vd.apply_profit_share(vault_equity, &mut vault, &mut vp, now)
             .unwrap();
         vault.apply_fee(&mut vp, vault_equity, now).unwrap();
 
@@ -402,7 +402,7 @@ mod vault_fcn {
         while (vault.total_shares == 2000000000 + 100000000) && cnt < 400 {
             now += 60 * 60 * 24; // 1 day later
 
-            vd.apply_profit_share(vault_equity, &mut vault, &mut vp)
+            vd.apply_profit_share(vault_equity, &mut vault, &mut vp, now)
                 .unwrap();
             vault.apply_fee(&mut vp, vault_equity, now).unwrap();
             // crate::msg!("vault last ts: {} vs {}", vault.last_fee_update_ts, now);
@@ -488,7 +488,7 @@ mod vault_fcn {
         while (vault.total_shares == 2000000000 + 100000000) && cnt < 400 {
             now += 60 * 60 * 24; // 1 day later
 
-            vd.apply_profit_share(vault_equity, &mut vault, &mut vp)
+            vd.apply_profit_share(vault_equity, &mut vault, &mut vp, now)
                 .unwrap();
             vault.apply_fee(&mut vp, vault_equity, now).unwrap();
             // crate::msg!("vault last ts: {} vs {}", vault.last_fee_update_ts, now);
@@ -1318,7 +1318,7 @@ mod vault_v1_fcn {
 
         now += 60 * 60 * 24; // 1 day later
 
-        vd.apply_profit_share(vault_equity, &mut vault, &mut Some(vp.borrow_mut()))
+        vd.apply_profit_share(vault_equity, &mut vault, &mut Some(vp.borrow_mut()), now)
             .unwrap();
         vault
             .apply_fee(&mut Some(vp.borrow_mut()), vault_equity, now)
@@ -1398,7 +1398,7 @@ mod vault_v1_fcn {
         while (vault.total_shares == 2000000000 + 100000000) && cnt < 400 {
             now += 60 * 60 * 24; // 1 day later
 
-            vd.apply_profit_share(vault_equity, &mut vault, &mut Some(vp.borrow_mut()))
+            vd.apply_profit_share(vault_equity, &mut vault, &mut Some(vp.borrow_mut()), now)
                 .unwrap();
             vault
                 .apply_fee(&mut Some(vp.borrow_mut()), vault_equity, now)
@@ -1500,7 +1500,7 @@ mod vault_v1_fcn {
         while (vault.total_shares == 2_000_000_000 + 100_000_000) && cnt < 400 {
             now += 60 * 60 * 24; // 1 day later
 
-            vd.apply_profit_share(vault_equity, &mut vault, &mut Some(vp.borrow_mut()))
+            vd.apply_profit_share(vault_equity, &mut vault, &mut Some(vp.borrow_mut()), now)
                 .unwrap();
             vault
                 .apply_fee(&mut Some(vp.borrow_mut()), vault_equity, now)
@@ -1608,7 +1608,7 @@ mod vault_v1_fcn {
         while (vault.total_shares == 2_000_000_000 + 100_000_000) && cnt < 400 {
             now += 60 * 60 * 24; // 1 day later
 
-            vd.apply_profit_share(vault_equity, &mut vault, &mut Some(vp.borrow_mut()))
+            vd.apply_profit_share(vault_equity, &mut vault, &mut Some(vp.borrow_mut()), now)
                 .unwrap();
             vault
                 .apply_fee(&mut Some(vp.borrow_mut()), vault_equity, now)
@@ -3120,3 +3120,823 @@ mod full_vault_withdraw_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod hurdle_rate_tests {
+    use crate::state::traits::VaultDepositorBase;
+    use crate::state::{TokenizedVaultDepositor, Vault, VaultDepositor, VaultProtocol};
+    use crate::WithdrawUnit;
+    use anchor_lang::prelude::Pubkey;
+    use drift::math::constants::{ONE_YEAR, QUOTE_PRECISION_U64};
+    use std::cell::RefCell;
+
+    const HALF_YEAR: i64 = ONE_YEAR as i64 / 2;
+    const QUARTER_YEAR: i64 = ONE_YEAR as i64 / 4;
+    const ONE_MONTH: i64 = ONE_YEAR as i64 / 12;
+
+    #[test]
+    fn test_profit_share_below_and_above_hurdle() {
+        let mut now = 123456789;
+        let mut vault = Vault::default();
+        let vp = RefCell::new(VaultProtocol::default());
+        vault.profit_share = 200_000; // 20%
+        vault.hurdle_rate = 100_000; // 10% annualized
+        vp.borrow_mut().protocol_profit_share = 50_000; // 5%
+        vault.last_fee_update_ts = now;
+
+        let amount: u64 = 1000 * QUOTE_PRECISION_U64;
+        let vd =
+            &mut VaultDepositor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+        vd.deposit(amount, 0, &mut vault, &mut Some(vp.borrow_mut()), now)
+            .unwrap();
+        assert_eq!(vd.last_hurdle_ts, now);
+
+        // half a year later up 3%, below the 5% hurdle
+        now += HALF_YEAR;
+        let vault_equity = amount + 30 * QUOTE_PRECISION_U64;
+        let (manager_profit_share, protocol_profit_share) = vd
+            .apply_profit_share(vault_equity, &mut vault, &mut Some(vp.borrow_mut()), now)
+            .unwrap();
+        assert_eq!(manager_profit_share, 0);
+        assert_eq!(protocol_profit_share, 0);
+        assert_eq!(vd.cumulative_profit_share_amount, 0);
+        assert_eq!(vd.hurdle_amount, 50 * QUOTE_PRECISION_U64);
+        assert_eq!(vd.last_hurdle_ts, now);
+
+        // another quarter later up 10%, 2.5% above the 7.5% hurdle
+        now += QUARTER_YEAR;
+        let vault_equity = amount + 100 * QUOTE_PRECISION_U64;
+        let (manager_profit_share, protocol_profit_share) = vd
+            .apply_profit_share(vault_equity, &mut vault, &mut Some(vp.borrow_mut()), now)
+            .unwrap();
+        assert_eq!(manager_profit_share, 5 * QUOTE_PRECISION_U64);
+        assert_eq!(protocol_profit_share, 1_250_000);
+        assert_eq!(vd.profit_share_fee_paid, 6_250_000);
+        // the high-water mark moves up by the full profit and the hurdle restarts from it
+        assert_eq!(
+            vd.cumulative_profit_share_amount,
+            100 * QUOTE_PRECISION_U64 as i64
+        );
+        assert_eq!(vd.hurdle_amount, 0);
+        assert_eq!(vault.manager_total_profit_share, 5 * QUOTE_PRECISION_U64);
+
+        // a month on the new 1100 high-water mark
+        now += ONE_MONTH;
+        let vault_equity = vault_equity + 9 * QUOTE_PRECISION_U64;
+        let (manager_profit_share, protocol_profit_share) = vd
+            .apply_profit_share(vault_equity, &mut vault, &mut Some(vp.borrow_mut()), now)
+            .unwrap();
+        assert_eq!(manager_profit_share, 0);
+        assert_eq!(protocol_profit_share, 0);
+        assert_eq!(vd.hurdle_amount, 9_166_666);
+    }
+
+    #[test]
+    fn test_hurdle_accrues_on_deposits_and_withdraws() {
+        let mut now = 123456789;
+        let mut vault = Vault::default();
+        let mut vp = None;
+        vault.profit_share = 200_000; // 20%
+        vault.hurdle_rate = 100_000; // 10% annualized
+        vault.last_fee_update_ts = now;
+
+        let amount: u64 = 1000 * QUOTE_PRECISION_U64;
+        let vd =
+            &mut VaultDepositor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+        vd.deposit(amount, 0, &mut vault, &mut vp, now).unwrap();
+        let mut vault_equity = amount;
+
+        // hurdle on the first 1000 for a quarter
+        now += QUARTER_YEAR;
+        vd.deposit(amount, vault_equity, &mut vault, &mut vp, now)
+            .unwrap();
+        vault_equity += amount;
+        assert_eq!(vd.hurdle_amount, 25 * QUOTE_PRECISION_U64);
+
+        // hurdle on 2000 for a quarter
+        now += QUARTER_YEAR;
+        vd.request_withdraw(
+            amount,
+            WithdrawUnit::Token,
+            vault_equity,
+            &mut vault,
+            &mut vp,
+            now,
+        )
+        .unwrap();
+        assert_eq!(vd.hurdle_amount, 75 * QUOTE_PRECISION_U64);
+
+        // hurdle on 2000 until the withdraw lowers the high-water mark
+        now += ONE_MONTH;
+        vd.withdraw(vault_equity, &mut vault, &mut vp, now).unwrap();
+        vault_equity -= amount;
+        assert_eq!(vd.hurdle_amount, 91_666_666);
+        assert_eq!(vd.last_hurdle_ts, now);
+
+        // remaining 1000 up 15% a month later, 5% above the 10% hurdle
+        now += ONE_MONTH;
+        let (manager_profit_share, _) = vd
+            .apply_profit_share(
+                vault_equity + 150 * QUOTE_PRECISION_U64,
+                &mut vault,
+                &mut vp,
+                now,
+            )
+            .unwrap();
+        assert_eq!(
+            manager_profit_share,
+            (150_000_000 - 91_666_666 - 8_333_333) / 5
+        );
+        assert_eq!(vd.hurdle_amount, 0);
+    }
+
+    #[test]
+    fn test_hurdle_starts_from_now_for_existing_depositor() {
+        let now = 123456789;
+        let mut vault = Vault::default();
+        vault.profit_share = 200_000; // 20%
+        vault.hurdle_rate = 100_000; // 10% annualized
+
+        let amount: u64 = 1000 * QUOTE_PRECISION_U64;
+        let vd =
+            &mut VaultDepositor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+        vd.deposit(amount, 0, &mut vault, &mut None, now).unwrap();
+        // depositor created before the hurdle was tracked
+        vd.last_hurdle_ts = 0;
+
+        let later = now + HALF_YEAR;
+        let (manager_profit_share, _) = vd
+            .apply_profit_share(
+                amount + 10 * QUOTE_PRECISION_U64,
+                &mut vault,
+                &mut None,
+                later,
+            )
+            .unwrap();
+        assert_eq!(manager_profit_share, 2 * QUOTE_PRECISION_U64);
+        assert_eq!(vd.last_hurdle_ts, later);
+    }
+
+    #[test]
+    fn test_tokenized_depositor_hurdle() {
+        let mut now = 123456789;
+        let mut vault = Vault::default();
+        vault.profit_share = 200_000; // 20%
+        vault.hurdle_rate = 80_000; // 8% annualized
+
+        let amount: u64 = 1000 * QUOTE_PRECISION_U64;
+        let mut tvd = TokenizedVaultDepositor::new(
+            Pubkey::default(),
+            Pubkey::default(),
+            Pubkey::default(),
+            0,
+            0,
+            now,
+        );
+        tvd.set_vault_shares(amount as u128);
+        tvd.net_deposits = amount as i64;
+        vault.user_shares = amount as u128;
+        vault.total_shares = amount as u128;
+
+        // 3 months at 8% is a 2% hurdle, up 1.5%
+        now += QUARTER_YEAR;
+        let (manager_profit_share, _) = tvd
+            .apply_profit_share(
+                amount + 15 * QUOTE_PRECISION_U64,
+                &mut vault,
+                &mut None,
+                now,
+            )
+            .unwrap();
+        assert_eq!(manager_profit_share, 0);
+        assert_eq!(tvd.hurdle_amount, 20 * QUOTE_PRECISION_U64);
+
+        // 1 more month is a 2.6666% hurdle, up 4%
+        now += ONE_MONTH;
+        let (manager_profit_share, _) = tvd
+            .apply_profit_share(
+                amount + 40 * QUOTE_PRECISION_U64,
+                &mut vault,
+                &mut None,
+                now,
+            )
+            .unwrap();
+        assert_eq!(manager_profit_share, (40_000_000 - 26_666_666) / 5);
+        assert_eq!(tvd.hurdle_amount, 0);
+        assert_eq!(
+            tvd.cumulative_profit_share_amount,
+            40 * QUOTE_PRECISION_U64 as i64
+        );
+    }
+}
+
+#[cfg(test)]
+mod crystallization_tests {
+    use crate::constants::ONE_DAY;
+    use crate::error::ErrorCode;
+    use crate::state::traits::VaultDepositorBase;
+    use crate::state::{TokenizedVaultDepositor, Vault, VaultDepositor};
+    use crate::WithdrawUnit;
+    use anchor_lang::prelude::Pubkey;
+    use drift::math::constants::QUOTE_PRECISION_U64;
+
+    const QUARTER: i64 = ONE_DAY * 90;
+
+    fn quarterly_vault() -> Vault {
+        let mut vault = Vault::default();
+        vault.profit_share = 200_000; // 20%
+        vault.crystallization_period = QUARTER;
+        vault
+    }
+
+    #[test]
+    fn test_profit_share_crystallizes_at_period_end() {
+        let start = 123456789;
+        let mut vault = quarterly_vault();
+        let mut vp = None;
+
+        let amount: u64 = 1000 * QUOTE_PRECISION_U64;
+        let vd = &mut VaultDepositor::new(
+            Pubkey::default(),
+            Pubkey::default(),
+            Pubkey::default(),
+            start,
+        );
+        vd.deposit(amount, 0, &mut vault, &mut vp, start).unwrap();
+        assert_eq!(vd.crystallization_period_start, start);
+
+        // up 10% a month in, nothing crystallizes
+        let now = start + ONE_DAY * 30;
+        assert!(!vd.crystallization_due(&vault, now).unwrap());
+        let (manager_profit_share, _) = vd
+            .crystallize_profit_share(amount + 100 * QUOTE_PRECISION_U64, &mut vault, &mut vp, now)
+            .unwrap();
+        assert_eq!(manager_profit_share, 0);
+        assert_eq!(vd.cumulative_profit_share_amount, 0);
+
+        // gains mostly given back by the end of the quarter, only the 2% above the high-water mark is charged
+        let now = start + QUARTER + ONE_DAY * 5;
+        assert!(vd.crystallization_due(&vault, now).unwrap());
+        let (manager_profit_share, _) = vd
+            .crystallize_profit_share(amount + 20 * QUOTE_PRECISION_U64, &mut vault, &mut vp, now)
+            .unwrap();
+        assert_eq!(manager_profit_share, 4 * QUOTE_PRECISION_U64);
+        assert_eq!(
+            vd.cumulative_profit_share_amount,
+            20 * QUOTE_PRECISION_U64 as i64
+        );
+        assert_eq!(vd.crystallization_period_start, start + QUARTER);
+        assert!(!vd.crystallization_due(&vault, now).unwrap());
+    }
+
+    #[test]
+    fn test_crystallize_fees_requires_period() {
+        let start = 123456789;
+        let vd = VaultDepositor::new(
+            Pubkey::default(),
+            Pubkey::default(),
+            Pubkey::default(),
+            start,
+        );
+
+        // legacy vaults without a period can't be crystallized by a keeper at any time
+        let vault = Vault::default();
+        assert_eq!(
+            vd.validate_crystallize_fees(&vault, start),
+            Err(ErrorCode::NoCrystallizationPeriod.into())
+        );
+        assert_eq!(
+            vd.validate_crystallize_fees(&vault, start + QUARTER * 4),
+            Err(ErrorCode::NoCrystallizationPeriod.into())
+        );
+
+        let vault = quarterly_vault();
+        assert_eq!(
+            vd.validate_crystallize_fees(&vault, start + QUARTER - 1),
+            Err(ErrorCode::CrystallizationPeriodNotEnded.into())
+        );
+        assert!(vd
+            .validate_crystallize_fees(&vault, start + QUARTER)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_deposit_within_period_does_not_crystallize() {
+        let start = 123456789;
+        let mut vault = quarterly_vault();
+        let mut vp = None;
+
+        let amount: u64 = 1000 * QUOTE_PRECISION_U64;
+        let vd = &mut VaultDepositor::new(
+            Pubkey::default(),
+            Pubkey::default(),
+            Pubkey::default(),
+            start,
+        );
+        vd.deposit(amount, 0, &mut vault, &mut vp, start).unwrap();
+
+        let now = start + ONE_DAY * 30;
+        let vault_equity = amount + 100 * QUOTE_PRECISION_U64;
+        vd.deposit(amount, vault_equity, &mut vault, &mut vp, now)
+            .unwrap();
+        assert_eq!(vd.profit_share_fee_paid, 0);
+        assert_eq!(vault.manager_total_profit_share, 0);
+
+        // the gain made before the deposit is charged at the end of the period
+        let now = start + QUARTER;
+        let (manager_profit_share, _) = vd
+            .crystallize_profit_share(vault_equity + amount, &mut vault, &mut vp, now)
+            .unwrap();
+        assert_eq!(manager_profit_share, 20 * QUOTE_PRECISION_U64);
+        assert_eq!(vd.crystallization_period_start, start + QUARTER);
+    }
+
+    #[test]
+    fn test_withdraw_request_crystallizes_within_period() {
+        let start = 123456789;
+        let mut vault = quarterly_vault();
+        let mut vp = None;
+
+        let amount: u64 = 1000 * QUOTE_PRECISION_U64;
+        let vd = &mut VaultDepositor::new(
+            Pubkey::default(),
+            Pubkey::default(),
+            Pubkey::default(),
+            start,
+        );
+        vd.deposit(amount, 0, &mut vault, &mut vp, start).unwrap();
+
+        let now = start + ONE_DAY * 30;
+        vd.request_withdraw(
+            100 * QUOTE_PRECISION_U64,
+            WithdrawUnit::Token,
+            amount + 100 * QUOTE_PRECISION_U64,
+            &mut vault,
+            &mut vp,
+            now,
+        )
+        .unwrap();
+        assert_eq!(vd.profit_share_fee_paid, 20 * QUOTE_PRECISION_U64);
+        assert_eq!(
+            vd.cumulative_profit_share_amount,
+            100 * QUOTE_PRECISION_U64 as i64
+        );
+        // the period boundaries don't move
+        assert_eq!(vd.crystallization_period_start, start);
+    }
+
+    #[test]
+    fn test_crystallization_period_start_skips_missed_periods() {
+        let start = 123456789;
+        let mut vault = quarterly_vault();
+        let mut vp = None;
+
+        let amount: u64 = 1000 * QUOTE_PRECISION_U64;
+        let vd = &mut VaultDepositor::new(
+            Pubkey::default(),
+            Pubkey::default(),
+            Pubkey::default(),
+            start,
+        );
+        vd.deposit(amount, 0, &mut vault, &mut vp, start).unwrap();
+
+        let now = start + QUARTER * 3 + QUARTER / 2;
+        let (manager_profit_share, _) = vd
+            .crystallize_profit_share(amount + 50 * QUOTE_PRECISION_U64, &mut vault, &mut vp, now)
+            .unwrap();
+        assert_eq!(manager_profit_share, 10 * QUOTE_PRECISION_U64);
+        assert_eq!(vd.crystallization_period_start, start + QUARTER * 3);
+        assert!(vd.crystallization_due(&vault, start + QUARTER * 4).unwrap());
+    }
+
+    #[test]
+    fn test_tokenized_depositor_crystallization() {
+        let start = 123456789;
+        let mut vault = quarterly_vault();
+
+        let amount: u64 = 1000 * QUOTE_PRECISION_U64;
+        let mut tvd = TokenizedVaultDepositor::new(
+            Pubkey::default(),
+            Pubkey::default(),
+            Pubkey::default(),
+            0,
+            0,
+            start,
+        );
+        tvd.set_vault_shares(amount as u128);
+        tvd.net_deposits = amount as i64;
+        vault.user_shares = amount as u128;
+        vault.total_shares = amount as u128;
+
+        let vault_equity = amount + 100 * QUOTE_PRECISION_U64;
+        let (manager_profit_share, _) = tvd
+            .crystallize_profit_share(vault_equity, &mut vault, &mut None, start + ONE_DAY)
+            .unwrap();
+        assert_eq!(manager_profit_share, 0);
+
+        let (manager_profit_share, _) = tvd
+            .crystallize_profit_share(vault_equity, &mut vault, &mut None, start + QUARTER)
+            .unwrap();
+        assert_eq!(manager_profit_share, 20 * QUOTE_PRECISION_U64);
+        assert_eq!(tvd.crystallization_period_start, start + QUARTER);
+    }
+}
+
+#[cfg(test)]
+mod deposit_withdraw_fee_tests {
+    use crate::state::traits::VaultDepositorBase;
+    use crate::state::{Vault, VaultDepositor};
+    use crate::WithdrawUnit;
+    use anchor_lang::prelude::Pubkey;
+    use drift::math::constants::QUOTE_PRECISION_U64;
+    use drift::math::insurance::if_shares_to_vault_amount as depositor_shares_to_vault_amount;
+
+    #[test]
+    fn test_deposit_fee_paid_to_manager() {
+        let now = 123456789;
+        let mut vault = Vault::default();
+        let mut vp = None;
+        vault.deposit_fee = 10_000; // 1%
+
+        let amount: u64 = 1000 * QUOTE_PRECISION_U64;
+        let vd =
+            &mut VaultDepositor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+        vd.deposit(amount, 0, &mut vault, &mut vp, now).unwrap();
+
+        assert_eq!(vault.total_shares, amount as u128);
+        assert_eq!(vault.user_shares, 990 * QUOTE_PRECISION_U64 as u128);
+        assert_eq!(vd.get_vault_shares(), vault.user_shares);
+        assert_eq!(vd.net_deposits, amount as i64);
+        assert_eq!(vault.net_deposits, amount as i64);
+
+        let manager_amount = depositor_shares_to_vault_amount(
+            vault.total_shares - vault.user_shares,
+            vault.total_shares,
+            amount,
+        )
+        .unwrap();
+        assert_eq!(manager_amount, 10 * QUOTE_PRECISION_U64);
+
+        // a second deposit pays the fee at the current share price
+        let vault_equity = amount * 2;
+        vd.deposit(amount, vault_equity, &mut vault, &mut vp, now)
+            .unwrap();
+        assert_eq!(vault.total_shares, 1500 * QUOTE_PRECISION_U64 as u128);
+        assert_eq!(vault.user_shares, 1485 * QUOTE_PRECISION_U64 as u128);
+    }
+
+    #[test]
+    fn test_withdraw_fee_paid_to_manager() {
+        let now = 123456789;
+        let mut vault = Vault::default();
+        let mut vp = None;
+        vault.withdraw_fee = 5_000; // 0.5%
+
+        let amount: u64 = 1000 * QUOTE_PRECISION_U64;
+        let vd =
+            &mut VaultDepositor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+        vd.deposit(amount, 0, &mut vault, &mut vp, now).unwrap();
+        assert_eq!(vault.user_shares, amount as u128);
+
+        let withdraw_amount = 100 * QUOTE_PRECISION_U64;
+        vd.request_withdraw(
+            withdraw_amount,
+            WithdrawUnit::Token,
+            amount,
+            &mut vault,
+            &mut vp,
+            now,
+        )
+        .unwrap();
+        let (withdrawn, _) = vd.withdraw(amount, &mut vault, &mut vp, now).unwrap();
+
+        assert_eq!(withdrawn, 99_500_000);
+        assert_eq!(vd.total_withdraws, 99_500_000);
+        assert_eq!(vd.net_deposits, 900_500_000);
+        assert_eq!(vault.total_withdraws, 99_500_000);
+        assert_eq!(vault.user_shares, 900 * QUOTE_PRECISION_U64 as u128);
+        // the fee stays in the vault as manager shares
+        assert_eq!(vault.total_shares, 900_500_000);
+
+        let manager_amount = depositor_shares_to_vault_amount(
+            vault.total_shares - vault.user_shares,
+            vault.total_shares,
+            amount - withdrawn,
+        )
+        .unwrap();
+        assert_eq!(manager_amount, 500_000);
+    }
+}
+
+#[cfg(test)]
+mod early_withdraw_penalty_tests {
+    use std::cell::RefCell;
+    use std::sync::Once;
+
+    use crate::constants::ONE_DAY;
+    use crate::events::{VaultDepositorAction, VaultDepositorRecord};
+    use crate::state::traits::VaultDepositorBase;
+    use crate::state::{TokenizedVaultDepositor, Vault, VaultDepositor};
+    use crate::WithdrawUnit;
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+    use anchor_lang::{AnchorDeserialize, Discriminator};
+    use drift::math::constants::QUOTE_PRECISION_U64;
+
+    thread_local! {
+        static LOGGED_DATA: RefCell<Vec<Vec<u8>>> = RefCell::new(vec![]);
+    }
+
+    /// Captures the data emitted by `emit!` on the current test thread
+    struct LogDataCapture;
+
+    impl SyscallStubs for LogDataCapture {
+        fn sol_log_data(&self, data: &[&[u8]]) {
+            LOGGED_DATA.with(|logged| {
+                logged
+                    .borrow_mut()
+                    .extend(data.iter().map(|field| field.to_vec()))
+            });
+        }
+    }
+
+    fn capture_events() {
+        static INIT: Once = Once::new();
+        INIT.call_once(|| {
+            set_syscall_stubs(Box::new(LogDataCapture));
+        });
+        LOGGED_DATA.with(|logged| logged.borrow_mut().clear());
+    }
+
+    fn emitted_vault_depositor_records() -> Vec<VaultDepositorRecord> {
+        LOGGED_DATA.with(|logged| {
+            logged
+                .borrow()
+                .iter()
+                .filter(|data| data.starts_with(&VaultDepositorRecord::DISCRIMINATOR))
+                .map(|data| VaultDepositorRecord::try_from_slice(&data[8..]).unwrap())
+                .collect()
+        })
+    }
+
+    fn locked_vault() -> Vault {
+        let mut vault = Vault::default();
+        vault.min_lockup_period = ONE_DAY * 30;
+        vault.early_withdraw_penalty = 20_000; // 2%
+        vault
+    }
+
+    #[test]
+    fn test_early_withdraw_penalty_schedule() {
+        let deposit_ts = 123456789;
+        let vault = locked_vault();
+
+        let rate = |request_ts| {
+            vault
+                .early_withdraw_penalty_rate(deposit_ts, request_ts)
+                .unwrap()
+        };
+        assert_eq!(rate(deposit_ts), 20_000);
+        assert_eq!(rate(deposit_ts + ONE_DAY * 15), 10_000);
+        assert_eq!(rate(deposit_ts + ONE_DAY * 10), 13_333);
+        assert_eq!(rate(deposit_ts + ONE_DAY * 30), 0);
+        assert_eq!(rate(deposit_ts + ONE_DAY * 45), 0);
+
+        // depositors that never deposited since the lockup was introduced
+        assert_eq!(vault.early_withdraw_penalty_rate(0, deposit_ts).unwrap(), 0);
+
+        let no_lockup = Vault::default();
+        assert_eq!(
+            no_lockup
+                .early_withdraw_penalty_rate(deposit_ts, deposit_ts)
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_withdraw_within_lockup_pays_penalty() {
+        let deposit_ts = 123456789;
+        let mut vault = locked_vault();
+        let mut vp = None;
+
+        let amount: u64 = 1000 * QUOTE_PRECISION_U64;
+        let vd = &mut VaultDepositor::new(
+            Pubkey::default(),
+            Pubkey::default(),
+            Pubkey::default(),
+            deposit_ts,
+        );
+        vd.deposit(amount, 0, &mut vault, &mut vp, deposit_ts)
+            .unwrap();
+        assert_eq!(vd.last_deposit_ts, deposit_ts);
+
+        let now = deposit_ts + ONE_DAY * 10;
+        vd.request_withdraw(
+            100 * QUOTE_PRECISION_U64,
+            WithdrawUnit::Token,
+            amount,
+            &mut vault,
+            &mut vp,
+            now,
+        )
+        .unwrap();
+        // the penalty is set by when the withdraw was requested
+        capture_events();
+        let (withdrawn, _) = vd
+            .withdraw(amount, &mut vault, &mut vp, now + ONE_DAY * 30)
+            .unwrap();
+
+        assert_eq!(withdrawn, 100 * QUOTE_PRECISION_U64 - 1_333_300);
+        assert_eq!(vd.net_deposits, (amount - withdrawn) as i64);
+        // all the withdrawn shares are burned so the penalty stays with the remaining shares
+        assert_eq!(vault.total_shares, 900 * QUOTE_PRECISION_U64 as u128);
+        assert_eq!(vault.user_shares, 900 * QUOTE_PRECISION_U64 as u128);
+
+        let records = emitted_vault_depositor_records();
+        let penalty = records
+            .iter()
+            .find(|record| record.action == VaultDepositorAction::Penalty)
+            .unwrap();
+        assert_eq!(penalty.amount, 1_333_300);
+        assert_eq!(penalty.vault_shares_before, amount as u128);
+        assert_eq!(penalty.user_vault_shares_before, amount as u128);
+        assert_eq!(penalty.total_vault_shares_before, amount as u128);
+        assert_eq!(
+            penalty.vault_shares_after,
+            900 * QUOTE_PRECISION_U64 as u128
+        );
+        assert_eq!(
+            penalty.user_vault_shares_after,
+            900 * QUOTE_PRECISION_U64 as u128
+        );
+        assert_eq!(
+            penalty.total_vault_shares_after,
+            900 * QUOTE_PRECISION_U64 as u128
+        );
+    }
+
+    #[test]
+    fn test_withdraw_after_lockup_has_no_penalty() {
+        let deposit_ts = 123456789;
+        let mut vault = locked_vault();
+        let mut vp = None;
+
+        let amount: u64 = 1000 * QUOTE_PRECISION_U64;
+        let vd = &mut VaultDepositor::new(
+            Pubkey::default(),
+            Pubkey::default(),
+            Pubkey::default(),
+            deposit_ts,
+        );
+        vd.deposit(amount, 0, &mut vault, &mut vp, deposit_ts)
+            .unwrap();
+
+        let now = deposit_ts + ONE_DAY * 30;
+        vd.request_withdraw(
+            100 * QUOTE_PRECISION_U64,
+            WithdrawUnit::Token,
+            amount,
+            &mut vault,
+            &mut vp,
+            now,
+        )
+        .unwrap();
+        let (withdrawn, _) = vd.withdraw(amount, &mut vault, &mut vp, now).unwrap();
+        assert_eq!(withdrawn, 100 * QUOTE_PRECISION_U64);
+    }
+
+    #[test]
+    fn test_tokenize_within_lockup_is_rejected() {
+        let old_deposit_ts = 123456789;
+        let deposit_ts = old_deposit_ts + ONE_DAY * 60;
+        let mut vault = locked_vault();
+        let mut vp = None;
+
+        let amount: u64 = 1000 * QUOTE_PRECISION_U64;
+        let old_vd = &mut VaultDepositor::new(
+            Pubkey::default(),
+            Pubkey::default(),
+            Pubkey::default(),
+            old_deposit_ts,
+        );
+        old_vd
+            .deposit(amount, 0, &mut vault, &mut vp, old_deposit_ts)
+            .unwrap();
+        let vd = &mut VaultDepositor::new(
+            Pubkey::default(),
+            Pubkey::default(),
+            Pubkey::default(),
+            deposit_ts,
+        );
+        vd.deposit(amount, amount, &mut vault, &mut vp, deposit_ts)
+            .unwrap();
+
+        // shares redeemed into the old depositor would be withdrawn without a penalty,
+        // so the fresh deposit can't be tokenized while it is still locked up
+        let now = deposit_ts + ONE_DAY;
+        assert_eq!(
+            vault
+                .early_withdraw_penalty_rate(old_vd.last_deposit_ts, now)
+                .unwrap(),
+            0
+        );
+        assert!(vd.validate_tokenize_shares(&vault, now).is_err());
+        assert!(old_vd.validate_tokenize_shares(&vault, now).is_ok());
+
+        // once the lockup is over the shares can be tokenized
+        let now = deposit_ts + ONE_DAY * 30;
+        vd.validate_tokenize_shares(&vault, now).unwrap();
+        let mut tvd = TokenizedVaultDepositor::new(
+            Pubkey::default(),
+            Pubkey::default(),
+            Pubkey::default(),
+            0,
+            0,
+            now,
+        );
+        let (shares_transferred, _) = vd
+            .transfer_shares(
+                &mut tvd,
+                &mut vault,
+                &mut vp,
+                amount,
+                WithdrawUnit::Token,
+                2 * amount,
+                now,
+            )
+            .unwrap();
+        assert_eq!(shares_transferred, amount as u128);
+        assert_eq!(vd.get_vault_shares(), 0);
+    }
+}
+
+#[cfg(test)]
+mod allowlist_entry_tests {
+    use crate::constants::ONE_DAY;
+    use crate::state::{AllowlistEntry, Vault, VaultDepositor};
+    use anchor_lang::prelude::Pubkey;
+    use drift::math::constants::QUOTE_PRECISION_U64;
+
+    fn allowlist_entry(vault: Pubkey, authority: Pubkey) -> AllowlistEntry {
+        AllowlistEntry {
+            vault,
+            authority,
+            ..AllowlistEntry::default()
+        }
+    }
+
+    #[test]
+    fn test_allowlist_entry_validate() {
+        let now = 123456789;
+        let vault = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let mut entry = allowlist_entry(vault, authority);
+
+        // no expiry
+        assert!(entry.validate(&vault, &authority, now).is_ok());
+        assert!(entry
+            .validate(&Pubkey::new_unique(), &authority, now)
+            .is_err());
+        assert!(entry.validate(&vault, &Pubkey::new_unique(), now).is_err());
+
+        entry.expiry_ts = now + ONE_DAY;
+        assert!(entry.validate(&vault, &authority, now).is_ok());
+        assert!(entry
+            .validate(&vault, &authority, now + ONE_DAY - 1)
+            .is_ok());
+        assert!(entry.validate(&vault, &authority, now + ONE_DAY).is_err());
+    }
+
+    #[test]
+    fn test_allowlist_deposit_cap() {
+        let now = 123456789;
+        let mut vault = Vault::default();
+        let mut vp = None;
+        let mut entry = allowlist_entry(Pubkey::default(), Pubkey::default());
+
+        let vd =
+            &mut VaultDepositor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+        vd.deposit(1000 * QUOTE_PRECISION_U64, 0, &mut vault, &mut vp, now)
+            .unwrap();
+
+        // no cap
+        assert!(entry.validate_net_deposits(vd.net_deposits).is_ok());
+
+        entry.max_deposit_amount = 1000 * QUOTE_PRECISION_U64;
+        assert!(entry.validate_net_deposits(vd.net_deposits).is_ok());
+
+        vd.deposit(
+            QUOTE_PRECISION_U64,
+            1000 * QUOTE_PRECISION_U64,
+            &mut vault,
+            &mut vp,
+            now,
+        )
+        .unwrap();
+        assert!(entry.validate_net_deposits(vd.net_deposits).is_err());
+
+        // net deposits turn negative after withdrawing profits
+        assert!(entry.validate_net_deposits(-100).is_ok());
+    }
+}
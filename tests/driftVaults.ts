@@ -347,6 +347,7 @@ describe('driftVaults', () => {
 				driftState: await adminClient.getStatePublicKey(),
 				driftSpotMarketVault: adminClient.getSpotMarketAccount(0).vault,
 				driftProgram: adminClient.program.programId,
+				allowlistEntry: null,
 			})
 			.remainingAccounts(remainingAccounts)
 			.rpc();
@@ -772,6 +773,7 @@ describe('TestProtocolVaults', () => {
 				userTokenAccount: vdUserUSDCAccount,
 				driftSpotMarketVault: adminClient.getSpotMarketAccount(0).vault,
 				driftProgram: adminClient.program.programId,
+				allowlistEntry: null,
 			})
 			.remainingAccounts(remainingAccounts)
 			.rpc();
@@ -29,6 +29,21 @@ export function getVaultDepositorAddressSync(
 	)[0];
 }
 
+export function getAllowlistEntryAddressSync(
+	programId: PublicKey,
+	vault: PublicKey,
+	authority: PublicKey
+): PublicKey {
+	return PublicKey.findProgramAddressSync(
+		[
+			Buffer.from(anchor.utils.bytes.utf8.encode('allowlist_entry')),
+			vault.toBuffer(),
+			authority.toBuffer(),
+		],
+		programId
+	)[0];
+}
+
 export function getTokenVaultAddressSync(
 	programId: PublicKey,
 	vault: PublicKey
@@ -308,6 +308,82 @@ export type DriftVaults = {
 					name: 'systemProgram';
 					isMut: false;
 					isSigner: false;
+				},
+				{
+					name: 'allowlistEntry';
+					isMut: false;
+					isSigner: false;
+					isOptional: true;
+					docs: ['required if the vault is permissioned'];
+				}
+			];
+			args: [];
+		},
+		{
+			name: 'addToAllowlist';
+			accounts: [
+				{
+					name: 'vault';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'allowlistEntry';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'authority';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'manager';
+					isMut: true;
+					isSigner: true;
+				},
+				{
+					name: 'rent';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'systemProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [
+				{
+					name: 'params';
+					type: {
+						defined: 'AllowlistEntryParams';
+					};
+				}
+			];
+		},
+		{
+			name: 'removeFromAllowlist';
+			accounts: [
+				{
+					name: 'vault';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'allowlistEntry';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'authority';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'manager';
+					isMut: true;
+					isSigner: true;
 				}
 			];
 			args: [];
@@ -540,6 +616,13 @@ export type DriftVaults = {
 					name: 'tokenProgram';
 					isMut: false;
 					isSigner: false;
+				},
+				{
+					name: 'allowlistEntry';
+					isMut: false;
+					isSigner: false;
+					isOptional: true;
+					docs: ['required if the vault is permissioned'];
 				}
 			];
 			args: [
@@ -1548,6 +1631,57 @@ export type DriftVaults = {
 		}
 	];
 	accounts: [
+		{
+			name: 'allowlistEntry';
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'vault';
+						docs: ['The permissioned vault the entry belongs to'];
+						type: 'publicKey';
+					},
+					{
+						name: 'authority';
+						docs: [
+							'The vault depositor authority allowed to deposit into the vault. It is a pda of vault and authority'
+						];
+						type: 'publicKey';
+					},
+					{
+						name: 'maxDepositAmount';
+						docs: [
+							"The max net deposits of the authority's vault depositor, 0 means no cap"
+						];
+						type: 'u64';
+					},
+					{
+						name: 'expiryTs';
+						docs: [
+							'The ts after which the entry is no longer valid, 0 means the entry never expires'
+						];
+						type: 'i64';
+					},
+					{
+						name: 'bump';
+						docs: ['The bump for the allowlist entry pda'];
+						type: 'u8';
+					},
+					{
+						name: 'padding1';
+						type: {
+							array: ['u8', 7];
+						};
+					},
+					{
+						name: 'padding';
+						type: {
+							array: ['u64', 4];
+						};
+					}
+				];
+			};
+		},
 		{
 			name: 'tokenizedVaultDepositor';
 			type: {
@@ -2037,6 +2171,22 @@ export type DriftVaults = {
 		}
 	];
 	types: [
+		{
+			name: 'AllowlistEntryParams';
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'maxDepositAmount';
+						type: 'u64';
+					},
+					{
+						name: 'expiryTs';
+						type: 'i64';
+					}
+				];
+			};
+		},
 		{
 			name: 'InitializeTokenizedVaultDepositorParams';
 			type: {
@@ -2717,6 +2867,31 @@ export type DriftVaults = {
 			code: 6023;
 			name: 'InvalidTokenization';
 			msg: 'InvalidTokenization';
+		},
+		{
+			code: 6024;
+			name: 'CrystallizationPeriodNotEnded';
+			msg: 'CrystallizationPeriodNotEnded';
+		},
+		{
+			code: 6025;
+			name: 'NotAllowlisted';
+			msg: 'NotAllowlisted';
+		},
+		{
+			code: 6026;
+			name: 'AllowlistEntryExpired';
+			msg: 'AllowlistEntryExpired';
+		},
+		{
+			code: 6027;
+			name: 'AllowlistDepositLimitExceeded';
+			msg: 'AllowlistDepositLimitExceeded';
+		},
+		{
+			code: 6028;
+			name: 'NoCrystallizationPeriod';
+			msg: 'NoCrystallizationPeriod';
 		}
 	];
 };
@@ -3032,6 +3207,82 @@ export const IDL: DriftVaults = {
 					isMut: false,
 					isSigner: false,
 				},
+				{
+					name: 'allowlistEntry',
+					isMut: false,
+					isSigner: false,
+					isOptional: true,
+					docs: ['required if the vault is permissioned'],
+				},
+			],
+			args: [],
+		},
+		{
+			name: 'addToAllowlist',
+			accounts: [
+				{
+					name: 'vault',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'allowlistEntry',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'authority',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'manager',
+					isMut: true,
+					isSigner: true,
+				},
+				{
+					name: 'rent',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [
+				{
+					name: 'params',
+					type: {
+						defined: 'AllowlistEntryParams',
+					},
+				},
+			],
+		},
+		{
+			name: 'removeFromAllowlist',
+			accounts: [
+				{
+					name: 'vault',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'allowlistEntry',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'authority',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'manager',
+					isMut: true,
+					isSigner: true,
+				},
 			],
 			args: [],
 		},
@@ -3264,6 +3515,13 @@ export const IDL: DriftVaults = {
 					isMut: false,
 					isSigner: false,
 				},
+				{
+					name: 'allowlistEntry',
+					isMut: false,
+					isSigner: false,
+					isOptional: true,
+					docs: ['required if the vault is permissioned'],
+				},
 			],
 			args: [
 				{
@@ -4271,6 +4529,57 @@ export const IDL: DriftVaults = {
 		},
 	],
 	accounts: [
+		{
+			name: 'allowlistEntry',
+			type: {
+				kind: 'struct',
+				fields: [
+					{
+						name: 'vault',
+						docs: ['The permissioned vault the entry belongs to'],
+						type: 'publicKey',
+					},
+					{
+						name: 'authority',
+						docs: [
+							'The vault depositor authority allowed to deposit into the vault. It is a pda of vault and authority',
+						],
+						type: 'publicKey',
+					},
+					{
+						name: 'maxDepositAmount',
+						docs: [
+							"The max net deposits of the authority's vault depositor, 0 means no cap",
+						],
+						type: 'u64',
+					},
+					{
+						name: 'expiryTs',
+						docs: [
+							'The ts after which the entry is no longer valid, 0 means the entry never expires',
+						],
+						type: 'i64',
+					},
+					{
+						name: 'bump',
+						docs: ['The bump for the allowlist entry pda'],
+						type: 'u8',
+					},
+					{
+						name: 'padding1',
+						type: {
+							array: ['u8', 7],
+						},
+					},
+					{
+						name: 'padding',
+						type: {
+							array: ['u64', 4],
+						},
+					},
+				],
+			},
+		},
 		{
 			name: 'tokenizedVaultDepositor',
 			type: {
@@ -4760,6 +5069,22 @@ export const IDL: DriftVaults = {
 		},
 	],
 	types: [
+		{
+			name: 'AllowlistEntryParams',
+			type: {
+				kind: 'struct',
+				fields: [
+					{
+						name: 'maxDepositAmount',
+						type: 'u64',
+					},
+					{
+						name: 'expiryTs',
+						type: 'i64',
+					},
+				],
+			},
+		},
 		{
 			name: 'InitializeTokenizedVaultDepositorParams',
 			type: {
@@ -5441,5 +5766,30 @@ export const IDL: DriftVaults = {
 			name: 'InvalidTokenization',
 			msg: 'InvalidTokenization',
 		},
+		{
+			code: 6024,
+			name: 'CrystallizationPeriodNotEnded',
+			msg: 'CrystallizationPeriodNotEnded',
+		},
+		{
+			code: 6025,
+			name: 'NotAllowlisted',
+			msg: 'NotAllowlisted',
+		},
+		{
+			code: 6026,
+			name: 'AllowlistEntryExpired',
+			msg: 'AllowlistEntryExpired',
+		},
+		{
+			code: 6027,
+			name: 'AllowlistDepositLimitExceeded',
+			msg: 'AllowlistDepositLimitExceeded',
+		},
+		{
+			code: 6028,
+			name: 'NoCrystallizationPeriod',
+			msg: 'NoCrystallizationPeriod',
+		},
 	],
 };
@@ -17,6 +17,7 @@ import {
 import { BorshAccountsCoder, Program, ProgramAccount } from '@coral-xyz/anchor';
 import { DriftVaults } from './types/drift_vaults';
 import {
+	getAllowlistEntryAddressSync,
 	getTokenizedVaultAddressSync,
 	getTokenizedVaultMintAddressSync,
 	getInsuranceFundTokenVaultAddressSync,
@@ -996,6 +997,65 @@ export class VaultClient {
 		}
 	}
 
+	/**
+	 * Allowlists a vault depositor authority for a permissioned vault.
+	 * @param vault permissioned vault address
+	 * @param authority vault depositor authority to allowlist
+	 * @param params max net deposits (0 for no cap) and expiry ts (0 for no expiry)
+	 * @returns
+	 */
+	public async addToAllowlist(
+		vault: PublicKey,
+		authority: PublicKey,
+		params: {
+			maxDepositAmount: BN;
+			expiryTs: BN;
+		},
+		uiTxParams?: TxParams
+	): Promise<TransactionSignature> {
+		const ix = this.program.instruction.addToAllowlist(params, {
+			accounts: {
+				vault,
+				allowlistEntry: getAllowlistEntryAddressSync(
+					this.program.programId,
+					vault,
+					authority
+				),
+				authority,
+				manager: this.driftClient.wallet.publicKey,
+				rent: SYSVAR_RENT_PUBKEY,
+				systemProgram: SystemProgram.programId,
+			},
+		});
+		return this.createAndSendTxn([ix], uiTxParams);
+	}
+
+	/**
+	 * Removes a vault depositor authority from the allowlist of a permissioned vault.
+	 * @param vault permissioned vault address
+	 * @param authority vault depositor authority to remove
+	 * @returns
+	 */
+	public async removeFromAllowlist(
+		vault: PublicKey,
+		authority: PublicKey,
+		uiTxParams?: TxParams
+	): Promise<TransactionSignature> {
+		const ix = this.program.instruction.removeFromAllowlist({
+			accounts: {
+				vault,
+				allowlistEntry: getAllowlistEntryAddressSync(
+					this.program.programId,
+					vault,
+					authority
+				),
+				authority,
+				manager: this.driftClient.wallet.publicKey,
+			},
+		});
+		return this.createAndSendTxn([ix], uiTxParams);
+	}
+
 	public async getApplyProfitShareIx(
 		vault: PublicKey,
 		vaultDepositor: PublicKey
@@ -1148,8 +1208,28 @@ export class VaultClient {
 		]);
 	}
 
+	/**
+	 * Returns the allowlist entry of the vault depositor authority if the vault is permissioned,
+	 * otherwise null so the optional account is omitted.
+	 */
+	private getAllowlistEntry(
+		vault: PublicKey,
+		permissioned: boolean,
+		authority: PublicKey
+	): PublicKey | null {
+		if (!permissioned) {
+			return null;
+		}
+		return getAllowlistEntryAddressSync(
+			this.program.programId,
+			vault,
+			authority
+		);
+	}
+
 	private createInitVaultDepositorIx(
 		vault: PublicKey,
+		permissioned: boolean,
 		authority?: PublicKey,
 		payer?: PublicKey
 	) {
@@ -1163,6 +1243,11 @@ export class VaultClient {
 			vaultDepositor,
 			vault,
 			authority: authority || this.driftClient.wallet.publicKey,
+			allowlistEntry: this.getAllowlistEntry(
+				vault,
+				permissioned,
+				authority || this.driftClient.wallet.publicKey
+			),
 		};
 
 		const initIx = this.program.instruction.initializeVaultDepositor({
@@ -1189,6 +1274,7 @@ export class VaultClient {
 		payer?: PublicKey,
 		uiTxParams?: TxParams
 	): Promise<TransactionSignature> {
+		const vaultAccount = await this.program.account.vault.fetch(vault);
 		const vaultDepositor = getVaultDepositorAddressSync(
 			this.program.programId,
 			vault,
@@ -1199,6 +1285,11 @@ export class VaultClient {
 			vaultDepositor,
 			vault,
 			authority: authority || this.driftClient.wallet.publicKey,
+			allowlistEntry: this.getAllowlistEntry(
+				vault,
+				vaultAccount.permissioned,
+				authority || this.driftClient.wallet.publicKey
+			),
 		};
 
 		if (this.cliMode) {
@@ -1212,7 +1303,12 @@ export class VaultClient {
 				})
 				.rpc();
 		} else {
-			const initIx = this.createInitVaultDepositorIx(vault, authority, payer);
+			const initIx = this.createInitVaultDepositorIx(
+				vault,
+				vaultAccount.permissioned,
+				authority,
+				payer
+			);
 			return await this.createAndSendTxn([initIx], uiTxParams);
 		}
 	}
@@ -1510,12 +1606,15 @@ export class VaultClient {
 		depositTokenAccount?: PublicKey
 	) {
 		let vaultPubKey: PublicKey;
+		let vaultDepositorAuthority: PublicKey;
 		if (initVaultDepositor) {
 			vaultPubKey = initVaultDepositor.vault;
+			vaultDepositorAuthority = initVaultDepositor.authority;
 		} else {
 			const vaultDepositorAccount =
 				await this.program.account.vaultDepositor.fetch(vaultDepositor);
 			vaultPubKey = vaultDepositorAccount.vault;
+			vaultDepositorAuthority = vaultDepositorAccount.authority;
 		}
 
 		const vaultAccount = await this.program.account.vault.fetch(vaultPubKey);
@@ -1575,6 +1674,11 @@ export class VaultClient {
 			userTokenAccount: userTokenAccount,
 			driftProgram: this.driftClient.program.programId,
 			tokenProgram: TOKEN_PROGRAM_ID,
+			allowlistEntry: this.getAllowlistEntry(
+				vaultPubKey,
+				vaultAccount.permissioned,
+				vaultDepositorAuthority
+			),
 		};
 
 		return {
@@ -1613,6 +1717,7 @@ export class VaultClient {
 			ixs.push(
 				this.createInitVaultDepositorIx(
 					vaultAccount.pubkey,
+					vaultAccount.permissioned,
 					initVaultDepositor.authority
 				)
 			);